@@ -0,0 +1,103 @@
+/// Criterion benchmarks of both parts of every solved day, on the real inputs in
+/// `data/inputs`. Days without an input are skipped.
+///
+/// Run `cargo bench`, or `cargo bench -- day05` for a single day. Reports, including HTML
+/// and CSV files, end up in `target/criterion`.
+use advent_of_code::{
+    day,
+    template::{read_file, runner::Part, Context, Day, Solution},
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// Includes the solution of each day as a module and benches its parts. Days implementing
+/// [`Solution`] name their type with `as`. `cargo scaffold` adds new days to the list below.
+macro_rules! days {
+    ($($day:literal => $module:ident: $path:literal $(as $solution:ident)?,)*) => {
+        $(
+            // `main` and `DAY` go unused here, and so do the imports of the tests, whose
+            // functions are left out of benchmarks. The days are linted in full as binaries.
+            #[allow(dead_code, unused_imports)]
+            #[path = $path]
+            mod $module;
+        )*
+
+        fn bench_days(c: &mut Criterion) {
+            $( bench_entry!(c, $day, $module $(as $solution)?); )*
+        }
+    };
+}
+
+/// Benches the free functions of a day, or the [`Solution`] it names with `as`.
+macro_rules! bench_entry {
+    ($c:expr, $day:literal, $module:ident) => {
+        bench_day($c, day!($day), $module::part_one, $module::part_two)
+    };
+    ($c:expr, $day:literal, $module:ident as $solution:ident) => {
+        bench_day(
+            $c,
+            day!($day),
+            $module::$solution::solve_part_one,
+            $module::$solution::solve_part_two,
+        )
+    };
+}
+
+days! {
+    1 => day01: "../src/bin/01.rs",
+    2 => day02: "../src/bin/02.rs",
+    3 => day03: "../src/bin/03.rs",
+    4 => day04: "../src/bin/04.rs",
+    5 => day05: "../src/bin/05.rs",
+    6 => day06: "../src/bin/06.rs",
+    7 => day07: "../src/bin/07.rs",
+    8 => day08: "../src/bin/08.rs",
+    9 => day09: "../src/bin/09.rs",
+    10 => day10: "../src/bin/10.rs",
+    11 => day11: "../src/bin/11.rs",
+    12 => day12: "../src/bin/12.rs",
+    13 => day13: "../src/bin/13.rs",
+    14 => day14: "../src/bin/14.rs",
+    15 => day15: "../src/bin/15.rs",
+    16 => day16: "../src/bin/16.rs",
+    17 => day17: "../src/bin/17.rs",
+    18 => day18: "../src/bin/18.rs",
+    19 => day19: "../src/bin/19.rs" as Aplenty,
+    20 => day20: "../src/bin/20.rs",
+    21 => day21: "../src/bin/21.rs",
+    22 => day22: "../src/bin/22.rs",
+    // new days go above this line
+}
+
+/// Benches both parts of `day` as `dayDD/part_one` and `dayDD/part_two`.
+fn bench_day<M1, M2>(
+    c: &mut Criterion,
+    day: Day,
+    part_one: impl Part<M1>,
+    part_two: impl Part<M2>,
+) {
+    let input = match read_file("inputs", day) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Skipping day {day}: {err}");
+            return;
+        }
+    };
+    let ctx = Context::new();
+
+    let mut group = c.benchmark_group(format!("day{day}"));
+    group.bench_function("part_one", |b| {
+        b.iter(|| part_one.solve(black_box(&input), &ctx));
+    });
+    group.bench_function("part_two", |b| {
+        b.iter(|| part_two.solve(black_box(&input), &ctx));
+    });
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    // some days take a good part of a second, 10 samples keep `cargo bench` bearable
+    config = Criterion::default().sample_size(10);
+    targets = bench_days
+}
+criterion_main!(benches);
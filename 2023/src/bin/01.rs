@@ -7,7 +7,7 @@ pub fn part_one(input: &str) -> Option<u32> {
                 .chars()
                 .filter_map(|x| x.to_digit(10));
             let first = chars.next().unwrap();
-            let last = match chars.last() {
+            let last = match chars.next_back() {
                 Some(x) => x,
                 None => first
             };
@@ -36,7 +36,7 @@ pub fn part_two(input: &str) -> Option<u32> {
                     res.to_digit(10)
                 });
             let first = chars.next().unwrap();
-            let last = match chars.last() {
+            let last = match chars.next_back() {
                 Some(x) => x,
                 None => first
             };
@@ -51,13 +51,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part("examples", DAY, 1));
+        let result = part_one(&advent_of_code::template::read_file_part("examples", DAY, 1).unwrap());
         assert_eq!(result, Some(142));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part("examples", DAY, 2));
+        let result = part_two(&advent_of_code::template::read_file_part("examples", DAY, 2).unwrap());
         assert_eq!(result, Some(281));
     }
 }
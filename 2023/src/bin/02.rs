@@ -42,27 +42,27 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 #[derive(Debug)]
-struct RGB (u32, u32, u32);
+struct Rgb (u32, u32, u32);
 
 // red 1
-fn cube(input: &str) -> RGB {
+fn cube(input: &str) -> Rgb {
     let (num, color) = input.trim().split_once(' ').unwrap();
     let num: u32 = num.parse().unwrap();
     match color {
-        "red" => RGB(num, 0, 0),
-        "green" => RGB(0, num, 0),
-        "blue" => RGB(0, 0, num),
+        "red" => Rgb(num, 0, 0),
+        "green" => Rgb(0, num, 0),
+        "blue" => Rgb(0, 0, num),
         _ => panic!("incorrect value"),
     }
 }
 
 // red 1,...
-fn round(input: &str) -> RGB {
+fn round(input: &str) -> Rgb {
     input
         .split(',')
         .map(cube)
         .reduce(|acc, e| {
-            RGB(acc.0 + e.0, acc.1 + e.1, acc.2 + e.2)
+            Rgb(acc.0 + e.0, acc.1 + e.1, acc.2 + e.2)
         })
         .unwrap()
 }
@@ -96,13 +96,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(2286));
     }
 }
@@ -1,179 +1,81 @@
-use std::collections::{BTreeMap, BTreeSet};
+use advent_of_code::grid::Grid;
+use glam::IVec2;
 
 advent_of_code::solution!(3);
 
-#[derive(Debug)]
-enum Value {
-    Dot,
-    Symbol,
-    Star,
-    Num(u32),
+/// A number of the schematic and the cells its digits cover.
+struct Number {
+    value: u32,
+    cells: Vec<IVec2>,
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let map = input
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            let mut prev_num: u32 = 0;
-            line.chars().enumerate().map(move |(x, ch)| {
-                (
-                    (y as i32, x as i32),
-                    match ch {
-                        '0'..='9' => {
-                            prev_num = prev_num * 10 + ch.to_digit(10).unwrap();
-                            Value::Num(prev_num)
-                        }
-                        '.' => {
-                            prev_num = 0;
-                            Value::Dot
-                        }
-                        _ => {
-                            prev_num = 0;
-                            Value::Symbol
-                        }
-                    },
-                )
-            })
-        })
-        .collect::<BTreeMap<(i32, i32), Value>>();
-    let mut visited = BTreeSet::<(i32, i32)>::new();
-    let (_, line) = input.lines().enumerate().last().unwrap();
-    let size_x: i32 = line.len().try_into().unwrap();
+impl Number {
+    fn is_adjacent(&self, pos: IVec2) -> bool {
+        self.cells
+            .iter()
+            .any(|&cell| (cell - pos).abs().max_element() <= 1)
+    }
+}
 
-    map.iter()
-        .flat_map(|((y, x), val)| {
-            let pos = [
-                (-1, -1),
-                (-1, 0),
-                (-1, 1),
-                (0, -1),
-                (0, 1),
-                (1, -1),
-                (1, 0),
-                (1, 1),
-            ];
-            let mut res: Vec<u32> = vec![];
-            if let Value::Symbol = val {
-                res = pos
+fn numbers(grid: &Grid<char>) -> Vec<Number> {
+    let mut numbers = vec![];
+    for (y, row) in grid.rows().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            let len = row[x..].iter().take_while(|c| c.is_ascii_digit()).count();
+            if len == 0 {
+                x += 1;
+                continue;
+            }
+            numbers.push(Number {
+                value: row[x..x + len]
                     .iter()
-                    .filter_map(|(yp, xp)| match map.get(&(y + yp, x + xp)).unwrap() {
-                        Value::Num(_) => {
-                            let fx = (x + xp..size_x)
-                                .take_while(|&xf| {
-                                    matches!(map.get(&(y + yp, xf)), Some(Value::Num(_)))
-                                })
-                                .last()
-                                .and_then(|xf| {
-                                    if !visited.contains(&(y + yp, xf)) {
-                                        visited.insert((y + yp, xf));
-                                        Some(xf)
-                                    } else {
-                                        None
-                                    }
-                                });
-                            match fx {
-                                Some(fx) => match map.get(&(y + yp, fx)).unwrap() {
-                                    Value::Num(fnum) => Some(*fnum),
-                                    _ => None,
-                                },
-                                None => None,
-                            }
-                        }
-                        _ => None,
-                    })
-                    .collect::<Vec<u32>>();
-            };
-            res
-        })
-        .reduce(|acc, el| acc + el)
+                    .fold(0, |acc, c| acc * 10 + c.to_digit(10).unwrap()),
+                cells: (x..x + len)
+                    .map(|x| IVec2::new(x as i32, y as i32))
+                    .collect(),
+            });
+            x += len;
+        }
+    }
+    numbers
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let map = input
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            let mut prev_num: u32 = 0;
-            line.chars().enumerate().map(move |(x, ch)| {
-                (
-                    (y as i32, x as i32),
-                    match ch {
-                        '0'..='9' => {
-                            prev_num = prev_num * 10 + ch.to_digit(10).unwrap();
-                            Value::Num(prev_num)
-                        }
-                        '.' => {
-                            prev_num = 0;
-                            Value::Dot
-                        }
-                        '*' => {
-                            prev_num = 0;
-                            Value::Star
-                        }
-                        _ => {
-                            prev_num = 0;
-                            Value::Symbol
-                        }
-                    },
-                )
+fn is_symbol(c: char) -> bool {
+    c != '.' && !c.is_ascii_digit()
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let grid: Grid<char> = input.parse().ok()?;
+    Some(
+        numbers(&grid)
+            .iter()
+            .filter(|number| {
+                number
+                    .cells
+                    .iter()
+                    .any(|&cell| grid.neighbours_8(cell).any(|(_, &c)| is_symbol(c)))
             })
-        })
-        .collect::<BTreeMap<(i32, i32), Value>>();
-    let mut visited = BTreeSet::<(i32, i32)>::new();
-    let (_, line) = input.lines().enumerate().last().unwrap();
-    let size_x: i32 = line.len().try_into().unwrap();
-    map.iter()
-        .flat_map(|((y, x), val)| {
-            let pos = [
-                (-1, -1),
-                (-1, 0),
-                (-1, 1),
-                (0, -1),
-                (0, 1),
-                (1, -1),
-                (1, 0),
-                (1, 1),
-            ];
-            let mut res: Vec<u32> = vec![];
-            if let Value::Star = val {
-                res = pos
+            .map(|number| number.value)
+            .sum(),
+    )
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let grid: Grid<char> = input.parse().ok()?;
+    let numbers = numbers(&grid);
+    Some(
+        grid.find_all(&'*')
+            .filter_map(|gear| {
+                let adjacent: Vec<u32> = numbers
                     .iter()
-                    .filter_map(|(yp, xp)| match map.get(&(y + yp, x + xp)).unwrap() {
-                        Value::Num(_) => {
-                            let fx = (x + xp..size_x)
-                                .take_while(|&xf| {
-                                    matches!(map.get(&(y + yp, xf)), Some(Value::Num(_)))
-                                })
-                                .last()
-                                .and_then(|xf| {
-                                    if !visited.contains(&(y + yp, xf)) {
-                                        visited.insert((y + yp, xf));
-                                        Some(xf)
-                                    } else {
-                                        None
-                                    }
-                                });
-                            match fx {
-                                Some(fx) => match map.get(&(y + yp, fx)).unwrap() {
-                                    Value::Num(fnum) => Some(*fnum),
-                                    _ => None,
-                                },
-                                None => None,
-                            }
-                        }
-                        _ => None,
-                    })
-                    .collect::<Vec<u32>>();
-                if res.len() != 2 {
-                    res = vec![];
-                } else {
-                    res = vec![res.iter().product()]
-                }
-            };
-            res
-        })
-        .reduce(|acc, el| acc + el)
+                    .filter(|number| number.is_adjacent(gear))
+                    .map(|number| number.value)
+                    .collect();
+                (adjacent.len() == 2).then(|| adjacent[0] * adjacent[1])
+            })
+            .sum(),
+    )
 }
 
 #[cfg(test)]
@@ -182,13 +84,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(4361));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(467835));
     }
 }
@@ -1,7 +1,13 @@
-use std::collections::HashMap;
+use advent_of_code::math::count_below_zero;
 
 advent_of_code::solution!(6);
 
+/// Holding the button for `x` ms beats the record `s` of a race lasting `t` ms if
+/// `x * (t - x) > s`, i.e. `x^2 - t * x + s < 0`.
+fn ways_to_win(t: i64, s: i64) -> u64 {
+    count_below_zero(1, -t, s).expect("race does not fit into i64")
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
     let results = input
         .lines()
@@ -9,7 +15,7 @@ pub fn part_one(input: &str) -> Option<u32> {
         .map(|line| {
             line.split_ascii_whitespace()
                 .skip(1)
-                .map(|num| num.parse::<i32>().unwrap())
+                .map(|num| num.parse::<i64>().unwrap())
                 .collect::<Vec<_>>()
         })
         .collect::<Vec<_>>();
@@ -17,38 +23,9 @@ pub fn part_one(input: &str) -> Option<u32> {
     let res = results[0]
         .iter()
         .zip(results[1].iter())
-        .fold(1u32, |acc, (&t, &s)| {
-            if acc == 0 {
-                return 0;
-            }
-
-            let d = t.pow(2) - 4 * s;
-
-            if d <= 0 {
-                0
-            } else {
-                let d_sqr = f32::sqrt(d as f32);
-                let nearest_sqr = d_sqr.floor() as i32;
-                let squares = (
-                    (nearest_sqr, (nearest_sqr).pow(2)),
-                    (nearest_sqr + 1, (nearest_sqr + 1).pow(2)),
-                );
-                if d == squares.0 .1 || d == squares.1 .1 {
-                    let x = if d == squares.0 .1 {
-                        squares.0 .0
-                    } else {
-                        squares.1 .0
-                    };
-                    return acc * ((t + x) / 2 - (t - x) / 2 - 1) as u32;
-                }
-
-                let left_root = (t as f32 - d_sqr) / 2.0;
-                let right_root = (t as f32 + d_sqr) / 2.0;
-
-                acc * (right_root.floor() as u32 - left_root.ceil() as u32 + 1)
-            }
-        });
-    Some(res)
+        .map(|(&t, &s)| ways_to_win(t, s))
+        .product::<u64>();
+    u32::try_from(res).ok()
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
@@ -59,54 +36,69 @@ pub fn part_two(input: &str) -> Option<u64> {
         .map(|line| line.parse::<i64>().unwrap())
         .collect::<Vec<_>>();
 
-    let t = results[0];
-    let s = results[1];
+    Some(ways_to_win(results[0], results[1]))
+}
 
-    let d = t.pow(2) - 4 * s;
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use proptest::prelude::*;
 
-    if d <= 0 {
-        Some(0)
-    } else {
-        let d_sqr = f64::sqrt(d as f64);
-        let nearest_sqr = d_sqr.floor() as i64;
-        let squares = (
-            (nearest_sqr, (nearest_sqr).pow(2)),
-            (nearest_sqr + 1, (nearest_sqr + 1).pow(2)),
-        );
-        if d == squares.0 .1 || d == squares.1 .1 {
-            let x = if d == squares.0 .1 {
-                squares.0 .0
-            } else {
-                squares.1 .0
-            };
-            return Some(((t + x) / 2 - (t - x) / 2 - 1) as u64);
-        }
+    use super::*;
 
-        let left_root = (t as f64 - d_sqr) / 2.0;
-        let right_root = (t as f64 + d_sqr) / 2.0;
+    /// Counts the winning button times one by one.
+    fn brute_force(input: &str) -> Option<u32> {
+        let numbers: Vec<Vec<i64>> = input
+            .lines()
+            .map(|line| {
+                line.split_ascii_whitespace()
+                    .skip(1)
+                    .map(|n| n.parse().unwrap())
+                    .collect()
+            })
+            .collect();
+        let ways = numbers[0]
+            .iter()
+            .zip(&numbers[1])
+            .map(|(&t, &s)| (0..=t).filter(|x| x * (t - x) > s).count() as u64);
+        u32::try_from(ways.product::<u64>()).ok()
+    }
 
-        Some(right_root.floor() as u64 - left_root.ceil() as u64 + 1)
+    /// Races with records on or next to a possible distance, where rounding the roots of
+    /// the quadratic goes wrong first.
+    fn races() -> impl Strategy<Value = String> {
+        let race = (0..2000i64, 0.0..=1.0, -1..=1i64).prop_map(|(t, hold, delta)| {
+            let x = (t as f64 * hold) as i64;
+            (t, (x * (t - x) + delta).max(0))
+        });
+        prop::collection::vec(race, 1..4).prop_map(|races| {
+            let (times, records): (Vec<_>, Vec<_>) = races.into_iter().unzip();
+            format!(
+                "Time: {}\nDistance: {}\n",
+                times.iter().join(" "),
+                records.iter().join(" ")
+            )
+        })
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    advent_of_code::differential_test! {
+        part_one_matches_brute_force: part_one, brute_force, races()
+    }
 
     #[test]
     fn test_inp() {
-        let _ = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let _ = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
     }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(288));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(71503));
     }
 }
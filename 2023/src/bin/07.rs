@@ -1,3 +1,5 @@
+use advent_of_code::aoc_debug;
+
 advent_of_code::solution!(7);
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
@@ -64,13 +66,13 @@ fn get_hand(cards: &str) -> Hand {
 
     if a == b && b == c && c == d && d == e {
         Hand::Five(a0, b0, c0, d0, e0)
-    } else if (a == b && b == c && c == d) || (b == c && c == d && d == e) {
+    } else if b == c && c == d && (a == b || d == e) {
         Hand::Four(a0, b0, c0, d0, e0)
-    } else if (a == b && b == c && d == e) || (a == b && c == d && d == e) {
+    } else if a == b && d == e && (b == c || c == d) {
         Hand::FullHouse(a0, b0, c0, d0, e0)
-    } else if (a == b && b == c) || (b == c && c == d) || (c == d && d == e) {
+    } else if (b == c && (a == b || c == d)) || (c == d && d == e) {
         Hand::Three(a0, b0, c0, d0, e0)
-    } else if (a == b && c == d) || (a == b && d == e) || (b == c && d == e) {
+    } else if (a == b && (c == d || d == e)) || (b == c && d == e) {
         Hand::TwoPairs(a0, b0, c0, d0, e0)
     } else if a == b || b == c || c == d || d == e {
         Hand::Pair(a0, b0, c0, d0, e0)
@@ -87,7 +89,7 @@ pub fn part_one(input: &str) -> Option<u32> {
         .collect::<Vec<_>>();
     res.sort_by(|a, b| a.0.cmp(&b.0));
     let res = res.iter().enumerate().fold(0u32, |acc, el| {
-        println!("{el:?}");
+        aoc_debug!(2; "{el:?}");
         acc + (el.0 as u32 + 1) * el.1 .2
     });
     Some(res)
@@ -127,25 +129,25 @@ fn get_hand_part_two(cards: &str) -> Hand {
 
     if a == b && b == c && c == d && d == e {
         Hand::Five(a0, b0, c0, d0, e0)
-    } else if (a == b && b == c && c == d) || (b == c && c == d && d == e) {
+    } else if b == c && c == d && (a == b || d == e) {
         if a == Card::Joker {
             Hand::Five(a0, b0, c0, d0, e0)
         } else {
             Hand::Four(a0, b0, c0, d0, e0)
         }
-    } else if (a == b && b == c && d == e) || (a == b && c == d && d == e) {
+    } else if a == b && d == e && (b == c || c == d) {
         if a == Card::Joker {
             Hand::Five(a0, b0, c0, d0, e0)
         } else {
             Hand::FullHouse(a0, b0, c0, d0, e0)
         }
-    } else if (a == b && b == c) || (b == c && c == d) || (c == d && d == e) {
+    } else if (b == c && (a == b || c == d)) || (c == d && d == e) {
         if a == Card::Joker {
             Hand::Four(a0, b0, c0, d0, e0)
         } else {
             Hand::Three(a0, b0, c0, d0, e0)
         }
-    } else if (a == b && c == d) || (a == b && d == e) {
+    } else if a == b && (c == d || d == e) {
         if a == Card::Joker {
             Hand::Four(a0, b0, c0, d0, e0)
         } else {
@@ -178,7 +180,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         .collect::<Vec<_>>();
     res.sort_by(|a, b| a.0.cmp(&b.0));
     let res = res.iter().enumerate().fold(0u32, |acc, el| {
-        println!("{el:?}");
+        aoc_debug!(2; "{el:?}");
         acc + (el.0 as u32 + 1) * el.1 .2
     });
     Some(res)
@@ -196,18 +198,18 @@ KKJJT 220
 KK677 28
 QQQJA 483";
         let res = part_one(input);
-        println!("{:?}", res);
+        assert_eq!(res, Some(6632));
     }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(6440));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(5905));
     }
 }
@@ -1,15 +1,19 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
+use advent_of_code::{math::lcm_all, parse::key_value};
 use nom::{
-    character::complete::{self, alpha1, alphanumeric1, line_ending, one_of, space0},
-    combinator::{eof, opt},
-    multi::{fold_many1, many1, separated_list1},
-    sequence::{delimited, preceded, separated_pair, terminated},
+    character::complete::{self, alphanumeric1, line_ending, one_of, space0},
+    combinator::opt,
+    multi::{fold_many1, many1},
+    sequence::{delimited, separated_pair, terminated},
     IResult,
 };
 
 advent_of_code::solution!(8);
 
+type Entry<'a> = (&'a str, (&'a str, &'a str));
+type Graph<'a> = HashMap<&'a str, Entry<'a>>;
+
 fn next<'a>(node: (&'a str, &'a str), dir: char) -> &'a str {
     match dir {
         'L' => node.0,
@@ -18,10 +22,10 @@ fn next<'a>(node: (&'a str, &'a str), dir: char) -> &'a str {
     }
 }
 
-fn parse_entry(input: &str) -> IResult<&str, (&str, (&str, &str))> {
-    separated_pair(
+fn parse_entry(input: &str) -> IResult<&str, Entry<'_>> {
+    key_value(
         alphanumeric1,
-        delimited(space0, complete::char('='), space0),
+        "=",
         delimited(
             complete::char('('),
             separated_pair(
@@ -34,7 +38,7 @@ fn parse_entry(input: &str) -> IResult<&str, (&str, (&str, &str))> {
     )(input)
 }
 
-fn parse_graph(input: &str) -> IResult<&str, HashMap<&str, (&str, (&str, &str))>> {
+fn parse_graph(input: &str) -> IResult<&str, Graph<'_>> {
     fold_many1(
         terminated(parse_entry, opt(line_ending)),
         HashMap::new,
@@ -49,7 +53,7 @@ fn parse_directions(input: &str) -> IResult<&str, Vec<char>> {
     terminated(many1(one_of("RL")), line_ending)(input)
 }
 
-fn parse(input: &str) -> IResult<&str, (Vec<char>, HashMap<&str, (&str, (&str, &str))>)> {
+fn parse(input: &str) -> IResult<&str, (Vec<char>, Graph<'_>)> {
     separated_pair(parse_directions, line_ending, parse_graph)(input)
 }
 
@@ -101,23 +105,7 @@ pub fn part_two(input: &str) -> Option<usize> {
         })
         .collect::<Vec<usize>>();
 
-    Some(lcm(&res))
-}
-
-pub fn lcm(nums: &[usize]) -> usize {
-    if nums.len() == 1 {
-        return nums[0];
-    }
-    let a = nums[0];
-    let b = lcm(&nums[1..]);
-    a * b / gcd_of_two_numbers(a, b)
-}
-
-fn gcd_of_two_numbers(a: usize, b: usize) -> usize {
-    if b == 0 {
-        return a;
-    }
-    gcd_of_two_numbers(b, a % b)
+    lcm_all(&res)
 }
 
 #[cfg(test)]
@@ -126,21 +114,24 @@ mod tests {
 
     #[test]
     fn test_parse() {
-        println!("{}", gcd_of_two_numbers(3, 4));
+        assert_eq!(lcm_all(&[3, 4]), Some(12));
     }
 
     #[test]
     fn test_parse_graph() {
-        let input = &advent_of_code::template::read_file("examples", DAY);
-        let result = parse(input);
-        println!("{:?}", result);
+        let input = &advent_of_code::template::read_file_part("examples", DAY, 1).unwrap();
+        let (rest, (directions, graph)) = parse(input).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(directions, vec!['L', 'L', 'R']);
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph["BBB"], ("BBB", ("AAA", "ZZZ")));
     }
 
     #[test]
     fn test_parse_entry() {
         let input = "AAA = (BBB, BBB)";
         let result = parse_entry(input);
-        println!("{:?}", result);
+        assert_eq!(result, Ok(("", ("AAA", ("BBB", "BBB")))));
     }
 
     #[test]
@@ -158,22 +149,20 @@ mod tests {
 22Z = (22B, 22B)
 XXX = (XXX, XXX)";
         let result = part_two(input);
-        println!("{result:?}");
+        assert_eq!(result, Some(12));
     }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
+        let result =
+            part_one(&advent_of_code::template::read_file_part("examples", DAY, 1).unwrap());
         assert_eq!(result, Some(6));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
+        let result =
+            part_two(&advent_of_code::template::read_file_part("examples", DAY, 2).unwrap());
         assert_eq!(result, Some(6));
     }
 }
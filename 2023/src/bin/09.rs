@@ -1,48 +1,9 @@
-advent_of_code::solution!(9);
-
-fn gcd(a: i64, b: i64) -> i64 {
-    if b == 0 {
-        return a;
-    }
-    gcd(b, a % b)
-}
+use advent_of_code::math::binomial;
 
-// m * a / b = (m/gcd(m, b) * a) / (b / gcd(m, b))
-fn multiply(m: i64, a: i64, b: i64) -> i64 {
-    if m == 0 || b == 0 {
-        return 0;
-    }
-    let g = gcd(m, b);
-    (m / g * a) / (b / g)
-}
-
-fn binomial(n: i64, k: i64) -> i64 {
-    if n == 0 || k == 0 {
-        return 1;
-    }
-
-    // coefficients are simmetric
-    if k > n - k {
-        return binomial(n, n - k);
-    }
-
-    let mut res = 1;
-    let mut i = 1;
-    let mut n = n;
-
-    loop {
-        if i > k {
-            break;
-        }
-        res = multiply(res, n, i);
-        i += 1;
-        n -= 1;
-    }
-    res
-}
+advent_of_code::solution!(9);
 
 fn next_number(numbers: &[i64]) -> i64 {
-    let n = numbers.len() as i64;
+    let n = numbers.len() as u64;
     let mut mul = -1;
     numbers
         .iter()
@@ -50,7 +11,7 @@ fn next_number(numbers: &[i64]) -> i64 {
         .enumerate()
         .map(|(num, &el)| {
             mul *= -1;
-            mul * binomial(n, (num + 1) as i64) * el
+            mul * binomial(n, num as u64 + 1).unwrap() as i64 * el
         })
         .sum::<i64>()
 }
@@ -90,12 +51,12 @@ mod tests {
 
     #[test]
     fn test_binomial() {
-        assert_eq!(binomial(10, 5), 252);
-        assert_eq!(binomial(7, 3), 35);
-        assert_eq!(binomial(7, 4), 35);
-        assert_eq!(binomial(2, 1), 2);
-        assert_eq!(binomial(0, 0), 1);
-        assert_eq!(binomial(3, 0), 1);
+        assert_eq!(binomial(10, 5), Some(252));
+        assert_eq!(binomial(7, 3), Some(35));
+        assert_eq!(binomial(7, 4), Some(35));
+        assert_eq!(binomial(2, 1), Some(2));
+        assert_eq!(binomial(0, 0), Some(1));
+        assert_eq!(binomial(3, 0), Some(1));
     }
 
     #[test]
@@ -108,13 +69,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(114));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(2));
     }
 }
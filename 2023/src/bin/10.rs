@@ -1,7 +1,9 @@
-use core::num;
-use std::collections::HashSet;
-
-use itertools::Itertools;
+use advent_of_code::{
+    aoc_debug,
+    direction::{Direction, Pos},
+    grid::Grid,
+    polygon::Polygon,
+};
 
 advent_of_code::solution!(10);
 
@@ -11,26 +13,7 @@ advent_of_code::solution!(10);
 // .L-J.
 // .....
 
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn to_offset(&self) -> (i32, i32) {
-        match self {
-            Direction::Up => (-1, 0),
-            Direction::Down => (1, 0),
-            Direction::Left => (0, -1),
-            Direction::Right => (0, 1),
-        }
-    }
-}
-
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 enum Cell {
     LeftRight, // -
     LeftDown,  // 7
@@ -70,8 +53,7 @@ impl Cell {
         }
     }
 
-    fn direction(&self, from: &Direction) -> Option<Direction> {
-        // println!("-- {:?} {:?}", self, from);
+    fn direction(&self, from: Direction) -> Option<Direction> {
         match (from, self) {
             (Direction::Right, Cell::LeftRight) => Some(Direction::Right),
             (Direction::Left, Cell::LeftRight) => Some(Direction::Left),
@@ -96,187 +78,70 @@ impl Cell {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut current_loc = (0, 0);
-    let mut map = input
-        .lines()
-        .enumerate()
-        .map(|(i, line)| {
-            line.chars()
-                .enumerate()
-                .map(|(j, c)| {
-                    if c == 'S' {
-                        current_loc = (i, j)
-                    };
-                    Cell::from_char(c).unwrap()
-                })
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
+fn parse(input: &str) -> (Grid<Cell>, Pos) {
+    let map = Grid::parse(input, Cell::from_char).unwrap();
+    let start = map.find(&Cell::Animal).unwrap();
+    (map, start)
+}
 
-    let max_i = map.len() - 1;
-    let max_j = map[0].len() - 1;
+pub fn part_one(input: &str) -> Option<u32> {
+    let (map, mut current_loc) = parse(input);
 
     // find first direction
-    let mut dir = [
-        Direction::Up,
-        Direction::Down,
-        Direction::Left,
-        Direction::Right,
-    ]
-    .iter()
-    .find_map(|&d| match (current_loc, d) {
-        ((0, _), Direction::Up) => None,
-        ((i, _), Direction::Down) if i == max_i => None,
-        ((_, 0), Direction::Left) => None,
-        ((_, j), Direction::Right) if j == max_j => None,
-        _ => {
-            let offset = d.to_offset();
-            let cell = &map[(current_loc.0 as i32 + offset.0) as usize]
-                [(current_loc.1 as i32 + offset.1) as usize];
-            println!("- {:?} -> {:?}", cell, d);
-            cell.direction(&d).map(|_| d)
-        }
-    })
-    .unwrap();
+    let mut dir = Direction::ALL
+        .into_iter()
+        .find_map(|d| {
+            let cell = map.get(current_loc + d)?;
+            cell.direction(d).map(|_| d)
+        })
+        .unwrap();
 
-    println!("{:?}", current_loc);
-    println!("{:?}", dir);
-    input
-        .lines()
-        .for_each(|line| println!("{:?}", line.chars().collect::<Vec<_>>()));
+    aoc_debug!("start at {current_loc}, heading {dir:?}");
 
-    for steps in 0..((max_i + 1) * (max_j + 1)) {
-        let dir_offset = dir.to_offset();
-        println!("{:?} -- {:?}", current_loc, dir_offset);
-        current_loc = (
-            (current_loc.0 as i32 + dir_offset.0) as usize,
-            (current_loc.1 as i32 + dir_offset.1) as usize,
-        );
-        let current = &map[current_loc.0][current_loc.1];
+    for steps in 0..(map.width() * map.height()) {
+        current_loc += dir;
+        let current = &map[current_loc];
         if let Cell::Animal = current {
-            return Some((steps as u32 + 1) / 2);
+            return Some((steps as u32).div_ceil(2));
         }
-        dir = current.direction(&dir).unwrap();
-        println!("{:?} -> {:?}", current.to_char(), dir);
+        dir = current.direction(dir).unwrap();
+        aoc_debug!(2; "{} at {current_loc} -> {dir:?}", current.to_char());
     }
 
     None
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut current_loc = (0, 0);
-    let mut map = input
-        .lines()
-        .enumerate()
-        .map(|(i, line)| {
-            line.chars()
-                .enumerate()
-                .map(|(j, c)| {
-                    if c == 'S' {
-                        current_loc = (i, j)
-                    };
-                    Cell::from_char(c).unwrap()
-                })
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
-
-    let max_i = map.len() - 1;
-    let max_j = map[0].len() - 1;
+    let (map, mut current_loc) = parse(input);
 
     // find first direction
-    let dirs = [
-        Direction::Up,
-        Direction::Down,
-        Direction::Left,
-        Direction::Right,
-    ]
-    .iter()
-    .filter_map(|&d| match (current_loc, d) {
-        ((0, _), Direction::Up) => None,
-        ((i, _), Direction::Down) if i == max_i => None,
-        ((_, 0), Direction::Left) => None,
-        ((_, j), Direction::Right) if j == max_j => None,
-        _ => {
-            let offset = d.to_offset();
-            let cell = &map[(current_loc.0 as i32 + offset.0) as usize]
-                [(current_loc.1 as i32 + offset.1) as usize];
-            println!("- {:?} -> {:?}", cell, d);
-            cell.direction(&d).map(|_| d)
-        }
-    })
-    .collect_tuple::<(Direction, Direction)>()
-    .unwrap();
-
-    let mut dir = dirs.0;
-
-    map[current_loc.0][current_loc.1] = match dirs {
-        (Direction::Up, Direction::Down) => Cell::UpDown,
-        (Direction::Left, Direction::Right) => Cell::UpDown,
-        (Direction::Up, Direction::Left) => Cell::UpLeft,
-        (Direction::Up, Direction::Right) => Cell::RightUp,
-        (Direction::Down, Direction::Left) => Cell::LeftDown,
-        (Direction::Down, Direction::Right) => Cell::DownRight,
-        _ => panic!("no direction"),
-    };
-
-    map.iter()
-        .for_each(|line| println!("{:?}", line.iter().map(|x| x.to_char()).collect::<Vec<_>>()));
+    let mut dir = Direction::ALL
+        .into_iter()
+        .find(|&d| {
+            map.get(current_loc + d)
+                .is_some_and(|cell| cell.direction(d).is_some())
+        })
+        .unwrap();
 
-    let mut loop_cells = HashSet::from([current_loc]);
+    aoc_debug!(2; "{}", map.map(Cell::to_char));
 
     let initial_loc = current_loc;
+    let mut path = vec![current_loc];
 
-    for _ in 0..((max_i + 1) * (max_j + 1)) {
-        let dir_offset = dir.to_offset();
-        current_loc = (
-            (current_loc.0 as i32 + dir_offset.0) as usize,
-            (current_loc.1 as i32 + dir_offset.1) as usize,
-        );
+    for _ in 0..(map.width() * map.height()) {
+        current_loc += dir;
         if current_loc == initial_loc {
             break;
         }
-        loop_cells.insert(current_loc);
-        let current = &map[current_loc.0][current_loc.1];
-        dir = current.direction(&dir).unwrap();
+        path.push(current_loc);
+        dir = map[current_loc].direction(dir).unwrap();
     }
 
-    let mut inside = 0;
-
-    for (i, line) in map.iter().enumerate() {
-        let mut num_of_walls = 0;
-        let mut prev_corner = None;
-        for (j, c) in line.iter().enumerate() {
-            match c {
-                _ if !loop_cells.contains(&(i, j)) => {
-                    if num_of_walls % 2 != 0 {
-                        inside += 1;
-                    }
-                }
-                Cell::LeftRight => (),
-                Cell::RightUp | Cell::DownRight => prev_corner = Some(c),
-                Cell::LeftDown => {
-                    match prev_corner {
-                        Some(Cell::RightUp) => num_of_walls += 1,
-                        _ => num_of_walls += 2,
-                    }
-                    prev_corner = None;
-                }
-                Cell::UpLeft => {
-                    match prev_corner {
-                        Some(Cell::DownRight) => num_of_walls += 1,
-                        _ => num_of_walls += 2,
-                    }
-                    prev_corner = None;
-                }
-                Cell::UpDown => num_of_walls += 1,
-                _ => panic!("no direction"),
-            };
-        }
-    }
+    // the loop visits every tile of its boundary once, the enclosed tiles are the interior
+    // lattice points of the polygon through the tile centers.
+    let inside = Polygon::<i64>::from_positions(path).interior();
 
-    Some(inside)
+    u32::try_from(inside).ok()
 }
 
 #[cfg(test)]
@@ -285,7 +150,8 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result =
+            part_one(&advent_of_code::template::read_file_part("examples", DAY, 1).unwrap());
         assert_eq!(result, Some(8));
     }
 
@@ -302,7 +168,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(8));
     }
 }
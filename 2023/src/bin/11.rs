@@ -1,3 +1,4 @@
+use advent_of_code::{grid::Grid, template::Context};
 use itertools::Itertools;
 use std::collections::BTreeSet;
 
@@ -18,54 +19,36 @@ fn fold_columns(
 }
 
 fn solve(input: &str, expansion: u64) -> Option<u64> {
-    let fields = input
-        .lines()
-        .map(|line| line.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-
-    let mut empty_cols = BTreeSet::from_iter(0..fields.len());
-    let mut empty_rows = BTreeSet::from_iter(0..fields[0].len());
+    let grid: Grid<char> = input.parse().ok()?;
 
-    fields.iter().enumerate().for_each(|(i, col)| {
-        col.iter().enumerate().for_each(|(j, &c)| {
-            if c == '#' {
-                empty_cols.remove(&i);
-                empty_rows.remove(&j);
-            }
-        })
-    });
+    let empty_rows = grid
+        .rows()
+        .enumerate()
+        .filter_map(|(y, row)| (!row.contains(&'#')).then_some(y))
+        .collect::<BTreeSet<_>>();
+    let empty_cols = grid
+        .columns()
+        .enumerate()
+        .filter_map(|(x, mut col)| (!col.any(|&c| c == '#')).then_some(x))
+        .collect::<BTreeSet<_>>();
 
-    let cols = (0..fields.len()).fold(
-        Vec::with_capacity(fields.len()),
-        fold_columns(&empty_cols, expansion),
-    );
-    let rows = (0..fields[0].len()).fold(
-        Vec::with_capacity(fields[0].len()),
+    let rows = (0..grid.height()).fold(
+        Vec::with_capacity(grid.height()),
         fold_columns(&empty_rows, expansion),
     );
+    let cols = (0..grid.width()).fold(
+        Vec::with_capacity(grid.width()),
+        fold_columns(&empty_cols, expansion),
+    );
 
-    let galaxies = fields
-        .iter()
-        .enumerate()
-        .flat_map(|(i, col)| {
-            col.iter()
-                .enumerate()
-                .filter_map(|(j, &c)| {
-                    if c == '#' {
-                        Some((cols[i] + i as u64, j as u64 + rows[j]))
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<_>>()
-                .into_iter()
+    let galaxies = grid
+        .find_all(&'#')
+        .map(|pos| {
+            let (x, y) = (pos.x as usize, pos.y as usize);
+            (rows[y] + y as u64, cols[x] + x as u64)
         })
         .collect::<Vec<_>>();
 
-    println!("{cols:?}");
-    println!("{rows:?}");
-    println!("{galaxies:?}");
-
     let res = galaxies.iter().combinations(2).fold(0u64, |acc, el| {
         acc + el[0].0.abs_diff(el[1].0) + el[0].1.abs_diff(el[1].1)
     });
@@ -76,23 +59,90 @@ pub fn part_one(input: &str) -> Option<u64> {
     solve(input, 2)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    solve(input, 1000000)
+/// The example is expanded by a factor of 10, the real input by a million, unless the
+/// `expansion` parameter says otherwise.
+pub fn part_two(input: &str, ctx: &Context) -> Option<u64> {
+    solve(
+        input,
+        ctx.param_or("expansion", ctx.example_or(10, 1_000_000)),
+    )
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
+    /// Repeats every empty row and column `expansion` times, then sums the distances.
+    fn brute_force(input: &str, expansion: usize) -> Option<u64> {
+        let rows: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+        let width = rows[0].len();
+        let empty_col = |x: usize| rows.iter().all(|row| row[x] != '#');
+
+        let mut expanded = vec![];
+        for row in &rows {
+            let row: Vec<char> = (0..width)
+                .flat_map(|x| {
+                    let n = if empty_col(x) { expansion } else { 1 };
+                    std::iter::repeat_n(row[x], n)
+                })
+                .collect();
+            let n = if row.contains(&'#') { 1 } else { expansion };
+            expanded.extend(std::iter::repeat_n(row, n));
+        }
+
+        let galaxies: Vec<(usize, usize)> = expanded
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| row.iter().positions(|&c| c == '#').map(move |x| (x, y)))
+            .collect();
+        let res = galaxies
+            .iter()
+            .tuple_combinations()
+            .map(|(a, b)| (a.0.abs_diff(b.0) + a.1.abs_diff(b.1)) as u64)
+            .sum();
+        Some(res)
+    }
+
+    /// Small, sparse images, so that some rows and columns are empty.
+    fn images() -> impl Strategy<Value = String> {
+        (1..8usize, 1..8usize).prop_flat_map(|(width, height)| {
+            prop::collection::vec(prop::bool::weighted(0.2), width * height).prop_map(
+                move |cells| {
+                    cells
+                        .chunks(width)
+                        .map(|row| row.iter().map(|&g| if g { '#' } else { '.' }).collect())
+                        .collect::<Vec<String>>()
+                        .join("\n")
+                },
+            )
+        })
+    }
+
+    advent_of_code::differential_test! {
+        part_one_matches_brute_force: part_one, |input| brute_force(input, 2), images()
+    }
+
+    advent_of_code::differential_test! {
+        part_two_matches_brute_force:
+            |input| part_two(input, &Context::example().with_param("expansion", 5)),
+            |input| brute_force(input, 5),
+            images()
+    }
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(374));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1030));
+        let input = advent_of_code::template::read_file("examples", DAY).unwrap();
+        assert_eq!(part_two(&input, &Context::example()), Some(1030));
+
+        let ctx = Context::example().with_param("expansion", 100);
+        assert_eq!(part_two(&input, &ctx), Some(8410));
     }
 }
@@ -1,14 +1,18 @@
-use std::{collections::HashMap, iter};
+use std::iter;
 
+use advent_of_code::memo::Memo;
 use itertools::Itertools;
 
 advent_of_code::solution!(12);
 
+/// Arrangements by remaining springs, remaining groups and size of the current group.
+type Cache<'a> = Memo<(&'a [char], &'a [u32], u32), u64>;
+
 fn operational<'a>(
     chars: &'a [char],
     numbers: &'a [u32],
     count: u32,
-    cache: &mut HashMap<(&'a [char], &'a [u32], u32), u64>,
+    cache: &mut Cache<'a>,
 ) -> u64 {
     if numbers.first().is_some_and(|x| *x == count) {
         permutations(&chars[1..], &numbers[1..], 0, cache)
@@ -19,13 +23,8 @@ fn operational<'a>(
     }
 }
 
-fn damaged<'a>(
-    chars: &'a [char],
-    numbers: &'a [u32],
-    count: u32,
-    cache: &mut HashMap<(&'a [char], &'a [u32], u32), u64>,
-) -> u64 {
-    if numbers.first().map_or(true, |x| *x <= count) {
+fn damaged<'a>(chars: &'a [char], numbers: &'a [u32], count: u32, cache: &mut Cache<'a>) -> u64 {
+    if numbers.first().is_none_or(|x| *x <= count) {
         0
     } else {
         permutations(&chars[1..], numbers, count + 1, cache)
@@ -36,13 +35,9 @@ fn permutations<'a>(
     chars: &'a [char],
     numbers: &'a [u32],
     count: u32,
-    cache: &mut HashMap<(&'a [char], &'a [u32], u32), u64>,
+    cache: &mut Cache<'a>,
 ) -> u64 {
-    if let Some(cached) = cache.get(&(chars, numbers, count)) {
-        return *cached;
-    }
-
-    let num_of_permutations = match chars.first() {
+    cache.get_or_compute((chars, numbers, count), |cache| match chars.first() {
         Some('.') => operational(chars, numbers, count, cache),
         Some('#') => damaged(chars, numbers, count, cache),
         Some('?') => {
@@ -51,10 +46,7 @@ fn permutations<'a>(
         None if numbers.is_empty() || numbers == [count] => 1,
         None => 0,
         Some(_) => panic!("wrong character"),
-    };
-
-    cache.insert((chars, numbers, count), num_of_permutations);
-    num_of_permutations
+    })
 }
 
 fn solve_line(line: &str) -> Option<u64> {
@@ -62,7 +54,7 @@ fn solve_line(line: &str) -> Option<u64> {
 
     let template: Vec<char> = template.chars().collect();
     let numbers: Vec<u32> = numbers.split(',').map(|x| x.parse().unwrap()).collect();
-    let mut mem = HashMap::new();
+    let mut mem = Memo::new();
 
     Some(permutations(&template, &numbers, 0, &mut mem))
 }
@@ -74,8 +66,8 @@ pub fn part_one(input: &str) -> Option<u64> {
 
 fn expand_line(input: &str) -> String {
     let (template, numbers) = input.split_once(' ').unwrap();
-    let temlate = iter::repeat(template).take(5).join("?");
-    let numbers = iter::repeat(numbers).take(5).join(",");
+    let temlate = iter::repeat_n(template, 5).join("?");
+    let numbers = iter::repeat_n(numbers, 5).join(",");
     format!("{temlate} {numbers}")
 }
 
@@ -89,24 +81,78 @@ pub fn part_two(input: &str) -> Option<u64> {
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
+    /// Tries every way of filling in the unknown springs.
+    fn brute_force(input: &str) -> Option<u64> {
+        let res = input
+            .lines()
+            .map(|line| {
+                let (template, numbers) = line.split_once(' ').unwrap();
+                let numbers: Vec<usize> = numbers.split(',').map(|x| x.parse().unwrap()).collect();
+                let unknown = template.chars().filter(|&c| c == '?').count();
+                (0..1u32 << unknown)
+                    .filter(|mask| {
+                        let mut bits = (0..unknown).map(|i| mask & (1 << i) != 0);
+                        let springs: String = template
+                            .chars()
+                            .map(|c| match c {
+                                '?' if bits.next().unwrap() => '#',
+                                '?' => '.',
+                                c => c,
+                            })
+                            .collect();
+                        let groups: Vec<usize> = springs
+                            .split('.')
+                            .filter(|g| !g.is_empty())
+                            .map(str::len)
+                            .collect();
+                        groups == numbers
+                    })
+                    .count() as u64
+            })
+            .sum();
+        Some(res)
+    }
+
+    /// Rows of up to 12 springs with up to 4 groups, not all of them possible.
+    fn rows() -> impl Strategy<Value = String> {
+        let row = (
+            prop::collection::vec(prop::sample::select(vec!['.', '#', '?']), 1..=12),
+            prop::collection::vec(1..4u32, 1..=4),
+        )
+            .prop_map(|(springs, numbers)| {
+                format!(
+                    "{} {}",
+                    springs.into_iter().collect::<String>(),
+                    numbers.iter().join(",")
+                )
+            });
+        prop::collection::vec(row, 1..4).prop_map(|rows| rows.join("\n"))
+    }
+
+    advent_of_code::differential_test! {
+        part_one_matches_brute_force: part_one, brute_force, rows()
+    }
+
     #[test]
     fn test_expanded_line() {
         let input = ".??..??...?##. 1,1,3";
         let result = solve_line(&expand_line(input));
-        assert_eq!(result, Some(4));
+        assert_eq!(result, Some(16384));
     }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(21));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(525152));
     }
 }
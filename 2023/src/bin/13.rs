@@ -1,11 +1,10 @@
 use std::iter;
 
-use nom::{
-    character::complete::{line_ending, one_of},
-    multi::{many1, separated_list1},
-    sequence::tuple,
-    IResult,
+use advent_of_code::{
+    grid::Grid,
+    parse::{blocks, grid, parse_all, ParseError},
 };
+use nom::IResult;
 
 advent_of_code::solution!(13);
 
@@ -15,9 +14,11 @@ fn diff_num(first: &[char], second: &[char]) -> u32 {
         .count() as u32
 }
 
-fn is_mirror(above: &[Vec<char>], below: &[Vec<char>], is_smudge: bool) -> bool {
+/// Whether the rows of `field` mirror between `at - 1` and `at`.
+fn is_mirror(field: &Grid<char>, at: usize, is_smudge: bool) -> bool {
+    let above = (0..at).rev().map(|y| field.row(y));
     let mut diff = 0;
-    for (a, b) in iter::zip(above.iter().rev(), below) {
+    for (a, b) in iter::zip(above, field.rows().skip(at)) {
         if is_smudge {
             diff += diff_num(a, b);
             if diff > 1 {
@@ -30,29 +31,26 @@ fn is_mirror(above: &[Vec<char>], below: &[Vec<char>], is_smudge: bool) -> bool
     !is_smudge || diff == 1
 }
 
-fn detect_mirror(field: &[Vec<char>], is_smudge: bool) -> Option<u32> {
-    let mut prev = field.first().unwrap();
-
-    field.iter().enumerate().skip(1).find_map(|(i, x)| {
-        ((x == prev || is_smudge) && is_mirror(&field[0..i], &field[i..], is_smudge))
-            .then_some(i as u32)
-            .or_else(|| {
-                prev = x;
-                None
-            })
-    })
+fn detect_mirror(field: &Grid<char>, is_smudge: bool) -> Option<u32> {
+    field
+        .rows()
+        .zip(field.rows().skip(1))
+        .enumerate()
+        .find_map(|(i, (prev, x))| {
+            ((x == prev || is_smudge) && is_mirror(field, i + 1, is_smudge)).then_some(i as u32 + 1)
+        })
 }
 
-fn field(input: &str) -> IResult<&str, Vec<Vec<char>>> {
-    separated_list1(line_ending, many1(one_of("#.")))(input)
+fn field(input: &str) -> IResult<&str, Grid<char>> {
+    grid(|c| matches!(c, '#' | '.').then_some(c))(input)
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<Vec<Vec<char>>>> {
-    separated_list1(tuple((line_ending, line_ending)), field)(input)
+fn parse(input: &str) -> IResult<&str, Vec<Grid<char>>> {
+    blocks(field)(input)
 }
 
-fn solve(input: &str, is_smudge: bool) -> Option<u32> {
-    let (_, fields) = parse(input).unwrap();
+fn solve(input: &str, is_smudge: bool) -> Result<u32, ParseError> {
+    let fields = parse_all(parse, input)?;
     let res: u32 = fields
         .iter()
         .map(|x| {
@@ -60,23 +58,20 @@ fn solve(input: &str, is_smudge: bool) -> Option<u32> {
                 return f * 100;
             }
 
-            let rotated = (0..x[0].len())
-                .map(|i| x.iter().map(|row| row[i]).collect::<Vec<_>>())
-                .collect::<Vec<_>>();
-            if let Some(f) = detect_mirror(&rotated, is_smudge) {
+            if let Some(f) = detect_mirror(&x.transpose(), is_smudge) {
                 return f;
             }
             panic!("no mirrors found")
         })
         .sum();
-    Some(res)
+    Ok(res)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
     solve(input, false)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
     solve(input, true)
 }
 
@@ -86,13 +81,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(405));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
+        assert_eq!(result, Ok(405));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(400));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
+        assert_eq!(result, Ok(400));
     }
 }
@@ -1,27 +1,18 @@
-use std::{collections::HashMap, fmt::Display};
+use std::fmt::Display;
 
-use itertools::iproduct;
+use advent_of_code::{
+    cycle::find_cycle,
+    grid::Grid,
+    template::Context,
+    visualize::{Color, Frame},
+};
+use glam::IVec2;
 
 advent_of_code::solution!(14);
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let mut field = Field {
-        x: input.lines().count(),
-        y: input.find('\n').unwrap(),
-        field: input
-            .lines()
-            .enumerate()
-            .flat_map(|(i, line)| {
-                line.chars().enumerate().filter_map(move |(j, c)| match c {
-                    'O' => Some(((i, j), Rock::Movable)),
-                    '#' => Some(((i, j), Rock::Immovable)),
-                    _ => None,
-                })
-            })
-            .collect(),
-    };
-
-    field.spin_to_north();
+    let mut field = Field::parse(input)?;
+    field.spin(Direction::North);
     Some(field.weight())
 }
 
@@ -33,173 +24,122 @@ enum Direction {
     East,
 }
 
-#[derive(Clone, PartialEq)]
-enum Rock {
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum Cell {
     Movable,
     Immovable,
+    Empty,
 }
 
+impl Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Cell::Movable => write!(f, "O"),
+            Cell::Immovable => write!(f, "#"),
+            Cell::Empty => write!(f, "."),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
 struct Field {
-    x: usize,
-    y: usize,
-    field: HashMap<(usize, usize), Rock>,
+    grid: Grid<Cell>,
 }
 
 impl Display for Field {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for i in 0..self.x {
-            for j in 0..self.y {
-                match self.field.get(&(i, j)) {
-                    Some(Rock::Movable) => write!(f, "O")?,
-                    Some(Rock::Immovable) => write!(f, "#")?,
-                    None => write!(f, ".")?,
-                }
-            }
-            writeln!(f)?;
-        }
-        writeln!(f)
+        writeln!(f, "{}", self.grid)
     }
 }
 
 impl Field {
-    fn weight(&self) -> u32 {
-        self.field
-            .iter()
-            .filter(|(_, rock)| **rock == Rock::Movable)
-            .map(|(pos, _)| self.x - pos.0)
-            .sum::<usize>() as u32
+    fn frame(&self) -> Frame {
+        Frame::from_grid(&self.grid, |cell| match cell {
+            Cell::Movable => ('O', Color::Yellow),
+            Cell::Immovable => ('#', Color::Gray),
+            Cell::Empty => ('.', Color::Default),
+        })
     }
 
-    fn spin_to_north(&mut self) {
-        let mut positions = vec![0usize; self.y];
-
-        for (i, j) in iproduct!(0..self.x, 0..self.y) {
-            match self.field.get(&(i, j)) {
-                Some(Rock::Movable) => {
-                    if i != positions[j] {
-                        // println!("Remove {:?} and set it to {:?}", (i, j), (positions[j], j));
-                        self.field.remove(&(i, j));
-                        self.field.insert((positions[j], j), Rock::Movable);
-                    }
-                    positions[j] += 1;
-                }
-                Some(Rock::Immovable) => {
-                    positions[j] = i + 1;
-                }
-                None => {}
-            }
-        }
+    fn parse(input: &str) -> Option<Self> {
+        let grid = Grid::parse(input, |c| match c {
+            'O' => Some(Cell::Movable),
+            '#' => Some(Cell::Immovable),
+            '.' => Some(Cell::Empty),
+            _ => None,
+        })
+        .ok()?;
+        Some(Field { grid })
     }
 
-    fn spin_to_south(&mut self) {
-        let mut positions = vec![self.x - 1; self.y];
-
-        for (i, j) in iproduct!((0..self.x).rev(), 0..self.y) {
-            match self.field.get(&(i, j)) {
-                Some(Rock::Movable) => {
-                    if i != positions[j] {
-                        // println!("Remove {:?} and set it to {:?}", (i, j), (positions[j], j));
-                        self.field.remove(&(i, j));
-                        self.field.insert((positions[j], j), Rock::Movable);
-                    }
-                    (i > 0).then(|| positions[j] -= 1);
-                }
-                Some(Rock::Immovable) => {
-                    (i > 0).then(|| positions[j] = i - 1);
-                }
-                None => {}
-            }
-        }
-    }
-
-    fn spin_to_west(&mut self) {
-        let mut positions = vec![0; self.x];
-
-        for (i, j) in iproduct!(0..self.x, 0..self.y) {
-            match self.field.get(&(i, j)) {
-                Some(Rock::Movable) => {
-                    if j != positions[i] {
-                        // println!("Remove {:?} and set it to {:?}", (i, j), (positions[j], j));
-                        self.field.remove(&(i, j));
-                        self.field.insert((i, positions[i]), Rock::Movable);
-                    }
-                    positions[i] += 1;
-                }
-                Some(Rock::Immovable) => {
-                    positions[i] = j + 1;
-                }
-                None => {}
-            }
-        }
+    fn weight(&self) -> u32 {
+        self.grid
+            .find_all(&Cell::Movable)
+            .map(|pos| self.grid.height() as u32 - pos.y as u32)
+            .sum()
     }
 
-    fn spin_to_east(&mut self) {
-        let mut positions = vec![self.y - 1; self.x];
-
-        for (i, j) in iproduct!(0..self.x, (0..self.y).rev()) {
-            match self.field.get(&(i, j)) {
-                Some(Rock::Movable) => {
-                    if j != positions[i] {
-                        // println!("Remove {:?} and set it to {:?}", (i, j), (positions[j], j));
-                        self.field.remove(&(i, j));
-                        self.field.insert((i, positions[i]), Rock::Movable);
+    /// Rolls all movable rocks towards `dir`, walking every line from the edge they roll to.
+    fn spin(&mut self, dir: Direction) {
+        let (width, height) = (self.grid.width() as i32, self.grid.height() as i32);
+        let (starts, step): (Vec<IVec2>, IVec2) = match dir {
+            Direction::North => ((0..width).map(|x| IVec2::new(x, 0)).collect(), IVec2::Y),
+            Direction::South => (
+                (0..width).map(|x| IVec2::new(x, height - 1)).collect(),
+                IVec2::NEG_Y,
+            ),
+            Direction::West => ((0..height).map(|y| IVec2::new(0, y)).collect(), IVec2::X),
+            Direction::East => (
+                (0..height).map(|y| IVec2::new(width - 1, y)).collect(),
+                IVec2::NEG_X,
+            ),
+        };
+
+        for start in starts {
+            // the cell the next movable rock rolls to
+            let mut free = start;
+            let mut pos = start;
+            while let Some(&cell) = self.grid.get(pos) {
+                match cell {
+                    Cell::Movable => {
+                        self.grid[pos] = Cell::Empty;
+                        self.grid[free] = Cell::Movable;
+                        free += step;
                     }
-                    (j > 0).then(|| positions[i] -= 1);
-                }
-                Some(Rock::Immovable) => {
-                    (j > 0).then(|| positions[i] = j - 1);
+                    Cell::Immovable => free = pos + step,
+                    Cell::Empty => {}
                 }
-                None => {}
+                pos += step;
             }
         }
     }
-
-    fn spin(&mut self, dir: Direction) {
-        match dir {
-            Direction::North => self.spin_to_north(),
-            Direction::West => self.spin_to_west(),
-            Direction::South => self.spin_to_south(),
-            Direction::East => self.spin_to_east(),
-        }
-    }
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut field = Field {
-        x: input.lines().count(),
-        y: input.find('\n').unwrap(),
-        field: input
-            .lines()
-            .enumerate()
-            .flat_map(|(i, line)| {
-                line.chars().enumerate().filter_map(move |(j, c)| match c {
-                    'O' => Some(((i, j), Rock::Movable)),
-                    '#' => Some(((i, j), Rock::Immovable)),
-                    _ => None,
-                })
-            })
-            .collect(),
-    };
-
-    let rounds = 1_000_000_000;
-    let mut cycle_history = Vec::new();
-    let Some(loop_start) = (0..rounds).find_map(|_| {
-        cycle_history.push(field.field.clone());
-        field.spin(Direction::North);
-        field.spin(Direction::West);
-        field.spin(Direction::South);
-        field.spin(Direction::East);
-        cycle_history.iter().position(|prev| *prev == field.field)
-    }) else {
-        return Some(field.weight());
-    };
-
-    let loop_length = cycle_history.len() - loop_start;
-    let solution_index = loop_start + ((rounds - loop_start) % loop_length);
-
-    field.field = cycle_history[solution_index].clone();
+pub fn part_two(input: &str, ctx: &Context) -> Option<u32> {
+    let field = Field::parse(input)?;
+
+    let mut vis = ctx.visualizer();
+    let mut spins = 0;
+    let cycle = find_cycle(field, |field| {
+        let mut field = field.clone();
+        spins += 1;
+        for dir in [
+            Direction::North,
+            Direction::West,
+            Direction::South,
+            Direction::East,
+        ] {
+            field.spin(dir);
+            vis.frame(|| {
+                field
+                    .frame()
+                    .with_caption(format!("cycle {spins}, tilted {dir:?}"))
+            });
+        }
+        field
+    });
 
-    Some(field.weight())
+    Some(cycle.value_at(1_000_000_000, Field::weight))
 }
 
 #[cfg(test)]
@@ -208,13 +148,16 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(136));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(
+            &advent_of_code::template::read_file("examples", DAY).unwrap(),
+            &Context::example(),
+        );
         assert_eq!(result, Some(64));
     }
 }
@@ -1,21 +1,23 @@
-use std::{collections::HashMap, hash};
+use std::collections::HashMap;
 
-use itertools::enumerate;
 use nom::{
-    character::complete::{self, alpha1, digit1, line_ending, one_of},
-    combinator::{complete, opt},
-    multi::{many1, separated_list1},
-    sequence::{tuple, Tuple},
+    character::complete::{self, alpha1, one_of},
+    combinator::opt,
+    multi::separated_list1,
+    sequence::tuple,
     IResult,
 };
 
 advent_of_code::solution!(15);
 
-fn parse_tag(input: &str) -> IResult<&str, (&str, char, Option<u8>)> {
+/// A label, the operation and the focal length for `=`.
+type Step<'a> = (&'a str, char, Option<u8>);
+
+fn parse_tag(input: &str) -> IResult<&str, Step<'_>> {
     tuple((alpha1, one_of("=-"), opt(complete::u8)))(input)
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<(&str, char, Option<u8>)>> {
+fn parse(input: &str) -> IResult<&str, Vec<Step<'_>>> {
     separated_list1(complete::char(','), parse_tag)(input)
 }
 
@@ -67,13 +69,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(1320));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(145));
     }
 }
@@ -1,15 +1,13 @@
 use std::collections::HashSet;
 
-advent_of_code::solution!(16);
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Direction(i32, i32);
+use advent_of_code::{
+    direction::{Direction, Pos},
+    grid::Grid,
+    template::Context,
+    visualize::{Color, Frame, Visualizer},
+};
 
-const UP: Direction = Direction(-1, 0);
-const DOWN: Direction = Direction(1, 0);
-const LEFT: Direction = Direction(0, -1);
-const RIGHT: Direction = Direction(0, 1);
-const ANY: Direction = Direction(0, 0);
+advent_of_code::solution!(16);
 
 enum Cell {
     Empty,
@@ -39,62 +37,91 @@ enum ResultDirection {
 }
 
 impl Cell {
+    fn glyph(&self) -> char {
+        match self {
+            Cell::Empty => '.',
+            Cell::Mirror => '/',
+            Cell::MirrorBack => '\\',
+            Cell::SplitHor => '-',
+            Cell::SplitVert => '|',
+        }
+    }
+
     fn next_direction(&self, from: Direction) -> ResultDirection {
         match self {
             Cell::Empty => ResultDirection::Unidirectional(from),
-            Cell::Mirror => ResultDirection::Unidirectional(Direction(-from.1, -from.0)),
-            Cell::MirrorBack => ResultDirection::Unidirectional(Direction(from.1, from.0)),
-            Cell::SplitHor if from.0 == 0 => ResultDirection::Unidirectional(from),
-            Cell::SplitHor => ResultDirection::Bidirectional(LEFT, RIGHT),
-            Cell::SplitVert if from.1 == 0 => ResultDirection::Unidirectional(from),
-            Cell::SplitVert => ResultDirection::Bidirectional(UP, DOWN),
+            Cell::Mirror if from.is_horizontal() => {
+                ResultDirection::Unidirectional(from.turn_left())
+            }
+            Cell::Mirror => ResultDirection::Unidirectional(from.turn_right()),
+            Cell::MirrorBack if from.is_horizontal() => {
+                ResultDirection::Unidirectional(from.turn_right())
+            }
+            Cell::MirrorBack => ResultDirection::Unidirectional(from.turn_left()),
+            Cell::SplitHor if from.is_horizontal() => ResultDirection::Unidirectional(from),
+            Cell::SplitHor => ResultDirection::Bidirectional(Direction::Left, Direction::Right),
+            Cell::SplitVert if from.is_vertical() => ResultDirection::Unidirectional(from),
+            Cell::SplitVert => ResultDirection::Bidirectional(Direction::Up, Direction::Down),
         }
     }
 }
 
+type Cache = HashSet<(Option<Direction>, Pos)>;
+
+/// The field with the energized cells lit up and the head of the beam at `pos`.
+fn frame(field: &Grid<Cell>, cache: &Cache, pos: Pos) -> Frame {
+    let mut frame = Frame::from_grid(field, |cell| (cell.glyph(), Color::Gray));
+    for (dir, energized) in cache {
+        if dir.is_none() {
+            frame.paint(*energized, Color::Yellow);
+        }
+    }
+    frame.set(pos, '@', Color::Red);
+    frame.with_caption(format!(
+        "{} energized",
+        cache.iter().filter(|(dir, _)| dir.is_none()).count()
+    ))
+}
+
+/// Walks the beam, `None` in the cache marking a cell energized from any direction.
 fn walk(
-    field: &[Vec<Cell>],
-    size: (i32, i32),
-    mut pos: (i32, i32),
+    field: &Grid<Cell>,
+    mut pos: Pos,
     mut dir: Direction,
-    cache: &mut HashSet<(Direction, (i32, i32))>,
+    cache: &mut Cache,
+    vis: &mut Visualizer,
 ) -> u32 {
     let mut steps = 0;
 
-    if pos.0.clamp(0, size.0) != pos.0 || pos.1.clamp(0, size.1) != pos.1 {
+    if !field.contains(pos) {
         return steps;
     }
 
     loop {
-        // println!("Current pos: {:?}, dir: {:?}, steps: {}", pos, dir, steps);
-        if cache.contains(&(dir, pos)) {
+        if !cache.insert((Some(dir), pos)) {
             return steps;
         }
-        cache.insert((dir, pos));
 
-        if !cache.contains(&(ANY, pos)) {
+        if cache.insert((None, pos)) {
             steps += 1;
-            cache.insert((ANY, pos));
         }
+        vis.frame(|| frame(field, cache, pos));
 
-        let cell = &field[pos.0 as usize][pos.1 as usize];
-        match cell.next_direction(dir) {
+        match field[pos].next_direction(dir) {
             ResultDirection::Unidirectional(new_dir) => {
                 dir = new_dir;
-                pos = (pos.0 + dir.0, pos.1 + dir.1);
-                // println!("-- New pos: {:?}, dir: {:?}, steps: {}", pos, dir, steps);
-                if pos.0.clamp(0, size.0) != pos.0 || pos.1.clamp(0, size.1) != pos.1 {
+                pos += dir;
+                if !field.contains(pos) {
                     break;
                 }
 
-                if cache.contains(&(dir, pos)) {
+                if cache.contains(&(Some(dir), pos)) {
                     break;
                 }
             }
             ResultDirection::Bidirectional(new_dir_1, new_dir_2) => {
-                // println!("Found bidirectional");
-                steps += walk(field, size, pos, new_dir_1, cache)
-                    + walk(field, size, pos, new_dir_2, cache);
+                steps += walk(field, pos, new_dir_1, cache, vis)
+                    + walk(field, pos, new_dir_2, cache, vis);
                 break;
             }
         }
@@ -103,56 +130,51 @@ fn walk(
     steps
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let field: Vec<Vec<Cell>> = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| Cell::try_from(c).expect("wrong input"))
-                .collect::<Vec<Cell>>()
-        })
-        .collect();
+fn parse(input: &str) -> Grid<Cell> {
+    Grid::parse(input, |c| Cell::try_from(c).ok()).expect("wrong input")
+}
+
+pub fn part_one(input: &str, ctx: &Context) -> Option<u32> {
+    let field = parse(input);
 
-    let size = (field.len() as i32 - 1, field[0].len() as i32 - 1);
     let mut cache = HashSet::new();
-    Some(walk(&field, size, (0, 0), RIGHT, &mut cache))
+    let mut vis = ctx.visualizer();
+    Some(walk(
+        &field,
+        Pos::ZERO,
+        Direction::Right,
+        &mut cache,
+        &mut vis,
+    ))
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let field: Vec<Vec<Cell>> = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| Cell::try_from(c).expect("wrong input"))
-                .collect::<Vec<Cell>>()
-        })
-        .collect();
-
-    let size = (field.len() as i32 - 1, field[0].len() as i32 - 1);
-    [DOWN, LEFT, UP, RIGHT]
-        .iter()
-        .map(|&d| {
-            if d == DOWN {
-                (0..size.1)
-                    .map(|i| walk(&field, size, (0, i), d, &mut HashSet::new()))
-                    .max()
-                    .unwrap()
-            } else if d == UP {
-                (0..size.1)
-                    .map(|i| walk(&field, size, (size.0, i), d, &mut HashSet::new()))
-                    .max()
-                    .unwrap()
-            } else if d == RIGHT {
-                (0..size.0)
-                    .map(|j| walk(&field, size, (j, 0), d, &mut HashSet::new()))
-                    .max()
-                    .unwrap()
-            } else {
-                (0..size.0)
-                    .map(|j| walk(&field, size, (j, size.1), d, &mut HashSet::new()))
-                    .max()
-                    .unwrap()
-            }
+    let field = parse(input);
+
+    let (width, height) = (field.width() as i32, field.height() as i32);
+    let vertical = (0..width).flat_map(|x| {
+        [
+            (Pos::new(x, 0), Direction::Down),
+            (Pos::new(x, height - 1), Direction::Up),
+        ]
+    });
+    let horizontal = (0..height).flat_map(|y| {
+        [
+            (Pos::new(0, y), Direction::Right),
+            (Pos::new(width - 1, y), Direction::Left),
+        ]
+    });
+
+    vertical
+        .chain(horizontal)
+        .map(|(pos, dir)| {
+            walk(
+                &field,
+                pos,
+                dir,
+                &mut HashSet::new(),
+                &mut Visualizer::disabled(),
+            )
         })
         .max()
 }
@@ -163,13 +185,16 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", DAY).unwrap(),
+            &Context::example(),
+        );
         assert_eq!(result, Some(46));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(51));
     }
 }
@@ -1,153 +1,68 @@
-use std::{
-    cell::RefCell,
-    cmp::Reverse,
-    collections::{BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque},
-    ops::Add,
+use advent_of_code::{
+    direction::Pos,
+    grid::Grid,
+    search::{dijkstra, MoveLimits, Run},
 };
 
-use glam::{IVec2, UVec2};
-use pathfinding::directed::dijkstra::{self, dijkstra};
-
 advent_of_code::solution!(17);
 
-#[derive(Debug, Clone)]
-struct Node {
-    heat: u32,
-    weight: u32,
-    pos: IVec2,
-    tail: Box<VecDeque<IVec2>>,
-}
-
-impl Ord for Node {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.weight.cmp(&self.weight)
-    }
-}
-
-impl PartialOrd for Node {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(other.cmp(self))
-    }
+fn parse(input: &str) -> Grid<u32> {
+    Grid::parse(input, |c| c.to_digit(10)).expect("invalid map")
 }
 
-impl PartialEq for Node {
-    fn eq(&self, other: &Self) -> bool {
-        other.weight == self.weight
-    }
-}
-
-impl Eq for Node {}
-
-enum Direction {
-    RIGHT,
-    UP,
-    DOWN,
-}
-
-fn find_path(blocks: &[Vec<Node>]) -> u32 {
-    let end = IVec2::new(blocks.len() as i32 - 1, blocks[0].len() as i32 - 1);
-
-    let res = dijkstra(
-        &(IVec2::splat(0), VecDeque::from([IVec2::splat(0)])),
-        |(pos, tail)| {
-            [
-                IVec2::new(1, 0),
-                IVec2::new(-1, 0),
-                IVec2::new(0, 1),
-                IVec2::new(0, -1),
-            ]
-            .into_iter()
-            .filter_map(|diff| {
-                let new_pos = *pos + diff;
-
-                if new_pos.x.clamp(0, end.x) != new_pos.x || new_pos.y.clamp(0, end.y) != new_pos.y
-                {
-                    return None;
-                }
-
-                if tail.len() > 1 && new_pos == tail[1] {
-                    return None;
-                }
-
-                let mut new_tail = tail.clone();
-                new_tail.push_front(new_pos);
-
-                if new_tail.len() == 5 {
-                    let dir = new_tail[1] - new_tail[0];
-                    new_tail
-                        .iter()
-                        .enumerate()
-                        .skip(2)
-                        .find_map(|(i, &p)| (p - new_tail[i - 1] != dir).then_some(()))?;
-                    new_tail.pop_back();
-                }
-
-                Some((new_pos, new_tail))
-            })
-            .map(|n| (n.clone(), blocks[n.0.x as usize][n.0.y as usize].heat))
-            .collect::<Vec<_>>()
+/// The least heat lost on the way from the top left to the bottom right block, for a crucible
+/// moving `limits.min` to `limits.max` blocks in a line before it turns or stops.
+fn find_path(blocks: &Grid<u32>, limits: MoveLimits) -> Option<u32> {
+    let end = blocks.size() - Pos::ONE;
+
+    dijkstra(
+        [Run::start(Pos::ZERO)],
+        |run| {
+            limits
+                .successors(run)
+                .filter_map(|next| Some((next, *blocks.get(next.pos)?)))
         },
-        |(pos, _)| pos == &end,
+        |run| run.pos == end && limits.can_stop(run),
     )
-    .expect("No path found");
-
-    for v in res.0 {
-        println!("{:?}", v);
-    }
-
-    res.1
+    .cost()
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let blocks: Vec<Vec<Node>> = input
-        .lines()
-        .enumerate()
-        .map(|(i, line)| {
-            line.chars()
-                .enumerate()
-                .map(|(j, c)| Node {
-                    heat: c.to_digit(10).unwrap(),
-                    weight: u32::MAX,
-                    pos: IVec2::new(i as i32, j as i32),
-                    tail: Box::default(),
-                })
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
-
-    Some(find_path(&blocks))
+    find_path(&parse(input), MoveLimits::new(1, 3))
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    None
+    find_path(&parse(input), MoveLimits::new(4, 10))
 }
 
 #[cfg(test)]
 mod tests {
-    use std::cmp::Reverse;
-
-    use glam::IVec2;
-
     use super::*;
 
     #[test]
-    fn test_dijkstra() {
+    fn test_small_map() {
         let input = "21131
 91195
 99993";
-
-        let res = part_one(input).unwrap();
-        println!("{:?}", res);
+        assert_eq!(part_one(input), Some(16));
     }
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(102));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
+        assert_eq!(result, Some(94));
+    }
+
+    #[test]
+    fn test_part_two_second_example() {
+        let result =
+            part_two(&advent_of_code::template::read_file_part("examples", DAY, 2).unwrap());
+        assert_eq!(result, Some(71));
     }
 }
@@ -1,22 +1,29 @@
-use std::{cmp, collections::HashMap, ops::RangeInclusive};
+use std::{cmp, collections::HashMap, fmt::Display, fmt::Write, fs};
 
+use advent_of_code::{
+    day,
+    template::{Context, Day, Solution},
+};
 use nom::{
     bytes::complete::tag,
-    character::complete::{self, alpha1, digit1, line_ending, one_of},
-    combinator::{complete, opt},
-    multi::{fold_many1, many1, separated_list1},
-    sequence::{delimited, preceded, separated_pair, terminated, tuple, Tuple},
+    character::complete::{self, alpha1, line_ending, one_of},
+    combinator::opt,
+    multi::{fold_many1, separated_list1},
+    sequence::{delimited, preceded, separated_pair, terminated, tuple},
     IResult,
 };
 
-advent_of_code::solution!(19);
+advent_of_code::solution!(solution = Aplenty);
 
-struct Parts {
-    x: u64,
-    m: u64,
-    a: u64,
-    s: u64,
-}
+const CATEGORIES: [char; 4] = ['x', 'm', 'a', 's'];
+
+/// The ratings of a part, in the order of [`CATEGORIES`].
+type Part = [u64; 4];
+
+/// Half-open rating ranges, one per category.
+type Region = [(u64, u64); 4];
+
+const ALL_RATINGS: Region = [(1, 4001); 4];
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 enum Action {
@@ -25,124 +32,260 @@ enum Action {
     Workflow(String),
 }
 
-impl TryFrom<&str> for Action {
-    type Error = ();
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
+impl From<&str> for Action {
+    fn from(value: &str) -> Self {
         match value {
-            "A" => Ok(Action::Accept),
-            "R" => Ok(Action::Reject),
-            _ => Ok(Action::Workflow(value.to_string())),
+            "A" => Action::Accept,
+            "R" => Action::Reject,
+            _ => Action::Workflow(value.to_string()),
         }
     }
 }
 
-enum ConditionRangeResult {
-    Next(Action),
-    Reject,
-    Split((PartsRange, Action), PartsRange),
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::Accept => write!(f, "A"),
+            Action::Reject => write!(f, "R"),
+            Action::Workflow(name) => write!(f, "{name}"),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
-enum Condition {
-    Test(char, cmp::Ordering, u64, Action),
-    Target(Action),
+struct Test {
+    category: usize,
+    ord: cmp::Ordering,
+    value: u64,
+    target: Action,
 }
 
-impl Condition {
-    fn apply(&self, part: &Parts) -> Option<Action> {
-        match self {
-            Condition::Test(field, op, num, action) => {
-                let f = match field {
-                    'x' => part.x,
-                    'm' => part.m,
-                    'a' => part.a,
-                    's' => part.s,
-                    _ => unreachable!(),
-                };
-                (f.cmp(num) == *op).then_some(action.clone())
+impl Display for Test {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let op = if self.ord == cmp::Ordering::Less {
+            '<'
+        } else {
+            '>'
+        };
+        write!(f, "{}{op}{}", CATEGORIES[self.category], self.value)
+    }
+}
+
+/// The tests of a workflow in order, and where parts failing all of them go.
+#[derive(Debug, Clone)]
+struct Workflow {
+    tests: Vec<Test>,
+    fallback: Action,
+}
+
+pub struct System {
+    workflows: HashMap<String, Workflow>,
+    parts: Vec<Part>,
+}
+
+impl System {
+    /// The workflow graph in Graphviz DOT format, each edge labelled with its test.
+    fn to_dot(&self) -> String {
+        let mut names: Vec<&String> = self.workflows.keys().collect();
+        names.sort();
+
+        let mut dot = String::from("digraph workflows {\n");
+        dot.push_str("    A [shape=doublecircle, style=filled, fillcolor=palegreen];\n");
+        dot.push_str("    R [shape=doublecircle, style=filled, fillcolor=lightpink];\n");
+        for name in &names {
+            let shape = if name.as_str() == "in" {
+                "shape=box, style=filled, fillcolor=lightblue"
+            } else {
+                "shape=box"
+            };
+            let _ = writeln!(dot, "    {name} [{shape}];");
+        }
+        for name in names {
+            let workflow = &self.workflows[name];
+            for test in &workflow.tests {
+                let _ = writeln!(dot, "    {name} -> {} [label=\"{test}\"];", test.target);
+            }
+            let _ = writeln!(
+                dot,
+                "    {name} -> {} [label=\"else\", style=dashed];",
+                workflow.fallback
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// All workflows inlined into one binary decision tree, starting at `in`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Accept,
+    Reject,
+    /// Parts rated below `at` in `category` go `below`, the others `above`.
+    Split {
+        category: usize,
+        at: u64,
+        below: Box<Node>,
+        above: Box<Node>,
+    },
+}
+
+impl Node {
+    /// A split, or just one branch if both lead to the same outcome.
+    fn split(category: usize, at: u64, below: Node, above: Node) -> Node {
+        if below == above {
+            below
+        } else {
+            Node::Split {
+                category,
+                at,
+                below: Box::new(below),
+                above: Box::new(above),
+            }
+        }
+    }
+
+    fn accepts(&self, part: &Part) -> bool {
+        let mut node = self;
+        loop {
+            match node {
+                Node::Accept => return true,
+                Node::Reject => return false,
+                Node::Split {
+                    category,
+                    at,
+                    below,
+                    above,
+                } => node = if part[*category] < *at { below } else { above },
             }
-            Condition::Target(action) => Some(action.clone()),
         }
     }
 
-    fn test_range(&self, part: &PartsRange) -> ConditionRangeResult {
+    /// The disjoint regions of all ratings this tree accepts.
+    fn regions(&self) -> Vec<Region> {
+        let mut regions = vec![];
+        self.accepted(ALL_RATINGS, &mut regions);
+        regions
+    }
+
+    /// The disjoint regions of `region` this tree accepts.
+    fn accepted(&self, region: Region, regions: &mut Vec<Region>) {
         match self {
-            Condition::Test(field, op, num, action) => {
-                let f = match field {
-                    'x' => &part.x,
-                    'm' => &part.m,
-                    'a' => &part.a,
-                    's' => &part.s,
-                    _ => unreachable!(),
-                };
-
-                if f.contains(num) {
-                    let (mut part_low, mut part_high) = (part.clone(), part.clone());
-                    match op {
-                        cmp::Ordering::Greater => {
-                            let (low, high) = (*f.start()..=*num, *num + 1..=*f.end());
-                            part_low.set(*field, low);
-                            part_high.set(*field, high);
-                            ConditionRangeResult::Split((part_high, action.clone()), part_low)
-                        }
-                        cmp::Ordering::Less => {
-                            let (low, high) = (*f.start()..=*num - 1, *num..=*f.end());
-                            part_low.set(*field, low);
-                            part_high.set(*field, high);
-                            ConditionRangeResult::Split((part_low, action.clone()), part_high)
-                        }
-                        _ => unreachable!(),
-                    }
-                } else {
-                    match op {
-                        cmp::Ordering::Greater if f.start() > num => {
-                            ConditionRangeResult::Next(action.clone())
-                        }
-                        cmp::Ordering::Less if f.end() < num => {
-                            ConditionRangeResult::Next(action.clone())
-                        }
-                        _ => ConditionRangeResult::Reject,
-                    }
+            Node::Accept => regions.push(region),
+            Node::Reject => {}
+            Node::Split {
+                category,
+                at,
+                below,
+                above,
+            } => {
+                let (lo, hi) = region[*category];
+                if lo < *at {
+                    let mut part = region;
+                    part[*category].1 = hi.min(*at);
+                    below.accepted(part, regions);
+                }
+                if hi > *at {
+                    let mut part = region;
+                    part[*category].0 = lo.max(*at);
+                    above.accepted(part, regions);
                 }
             }
-            Condition::Target(action) => ConditionRangeResult::Next(action.clone()),
         }
     }
 }
 
-type Workflow = Box<dyn Fn(&Parts) -> Action>;
-struct Workflows(HashMap<Action, (Workflow, Vec<Condition>)>);
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompileError {
+    UnknownWorkflow(String),
+    /// The workflows on the way from `in` back to the first of them.
+    Cycle(Vec<String>),
+}
 
-impl Workflows {
-    fn new() -> Self {
-        Workflows(HashMap::new())
+impl Display for CompileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompileError::UnknownWorkflow(name) => write!(f, "unknown workflow \"{name}\""),
+            CompileError::Cycle(names) => write!(f, "workflows loop: {}", names.join(" -> ")),
+        }
     }
+}
 
-    fn run(&self, part: &Parts) -> bool {
-        let mut action = Action::Workflow("in".to_string());
-        while let Action::Workflow(_) = action {
-            let (action_run, _) = self.0.get(&action).unwrap();
-            action = action_run(part);
-        }
-        match action {
-            Action::Accept => true,
-            Action::Reject => false,
-            _ => unreachable!(),
-        }
+impl std::error::Error for CompileError {}
+
+/// Compiles the workflows reachable from `in` into a single decision tree.
+fn compile(workflows: &HashMap<String, Workflow>) -> Result<Node, CompileError> {
+    compile_action(&Action::from("in"), workflows, &mut vec![])
+}
+
+fn compile_action(
+    action: &Action,
+    workflows: &HashMap<String, Workflow>,
+    path: &mut Vec<String>,
+) -> Result<Node, CompileError> {
+    let name = match action {
+        Action::Accept => return Ok(Node::Accept),
+        Action::Reject => return Ok(Node::Reject),
+        Action::Workflow(name) => name,
+    };
+    if let Some(start) = path.iter().position(|n| n == name) {
+        let mut cycle = path[start..].to_vec();
+        cycle.push(name.clone());
+        return Err(CompileError::Cycle(cycle));
+    }
+    let workflow = workflows
+        .get(name)
+        .ok_or_else(|| CompileError::UnknownWorkflow(name.clone()))?;
+
+    path.push(name.clone());
+    // built from the fallback backwards, every test guarding the rest of the workflow
+    let mut node = compile_action(&workflow.fallback, workflows, path)?;
+    for test in workflow.tests.iter().rev() {
+        let target = compile_action(&test.target, workflows, path)?;
+        node = match test.ord {
+            cmp::Ordering::Less => Node::split(test.category, test.value, target, node),
+            _ => Node::split(test.category, test.value + 1, node, target),
+        };
     }
+    path.pop();
+    Ok(node)
+}
+
+fn volume(region: &Region) -> u64 {
+    region.iter().map(|(lo, hi)| hi - lo).product()
 }
 
-fn get_workflow(conditions: Vec<Condition>) -> Workflow {
-    Box::new(move |parts| {
-        conditions
+/// Accepted regions as CSV, with inclusive bounds per category.
+fn regions_csv(regions: &[Region]) -> String {
+    let mut csv = String::from("x_min,x_max,m_min,m_max,a_min,a_max,s_min,s_max\n");
+    for region in regions {
+        let bounds: Vec<String> = region
             .iter()
-            .find_map(|cond| cond.apply(parts))
-            .expect("incorrect workflow")
-    })
+            .map(|(lo, hi)| format!("{lo},{}", hi - 1))
+            .collect();
+        let _ = writeln!(csv, "{}", bounds.join(","));
+    }
+    csv
 }
 
-fn parse_condition(input: &str) -> IResult<&str, Condition> {
-    let (input, (name, op, value, action)) = tuple((
+/// Accepted regions as a JSON array of objects, with inclusive bounds per category.
+fn regions_json(regions: &[Region]) -> String {
+    let objects: Vec<String> = regions
+        .iter()
+        .map(|region| {
+            let fields: Vec<String> = CATEGORIES
+                .iter()
+                .zip(region)
+                .map(|(c, (lo, hi))| format!("\"{c}\": [{lo}, {}]", hi - 1))
+                .collect();
+            format!("  {{{}}}", fields.join(", "))
+        })
+        .collect();
+    format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+fn parse_test(input: &str) -> IResult<&str, Test> {
+    let (input, (category, op, value, target)) = tuple((
         one_of("xmas"),
         one_of("<>"),
         complete::u64,
@@ -155,35 +298,45 @@ fn parse_condition(input: &str) -> IResult<&str, Condition> {
     };
     Ok((
         input,
-        Condition::Test(name, ord, value, Action::try_from(action).unwrap()),
+        Test {
+            category: CATEGORIES.iter().position(|&c| c == category).unwrap(),
+            ord,
+            value,
+            target: Action::from(target),
+        },
     ))
 }
 
-fn parse_workflow_inside(input: &str) -> IResult<&str, (Workflow, Vec<Condition>)> {
-    let (input, (mut conditions, last_action)) =
-        separated_pair(separated_list1(tag(","), parse_condition), tag(","), alpha1)(input)?;
-    conditions.push(Condition::Target(Action::try_from(last_action).unwrap()));
-    Ok((input, (get_workflow(conditions.clone()), conditions)))
+fn parse_workflow_inside(input: &str) -> IResult<&str, Workflow> {
+    let (input, (tests, fallback)) =
+        separated_pair(separated_list1(tag(","), parse_test), tag(","), alpha1)(input)?;
+    Ok((
+        input,
+        Workflow {
+            tests,
+            fallback: Action::from(fallback),
+        },
+    ))
 }
 
-fn parse_workflow(input: &str) -> IResult<&str, (Action, (Workflow, Vec<Condition>))> {
-    let (input, (action, workflow)) =
+fn parse_workflow(input: &str) -> IResult<&str, (String, Workflow)> {
+    let (input, (name, workflow)) =
         tuple((alpha1, delimited(tag("{"), parse_workflow_inside, tag("}"))))(input)?;
-    Ok((input, (Action::try_from(action).unwrap(), workflow)))
+    Ok((input, (name.to_string(), workflow)))
 }
 
-fn parse_workflows(input: &str) -> IResult<&str, Workflows> {
+fn parse_workflows(input: &str) -> IResult<&str, HashMap<String, Workflow>> {
     fold_many1(
         terminated(parse_workflow, line_ending),
-        Workflows::new,
-        |mut acc: Workflows, (name, func)| {
-            acc.0.insert(name, func);
+        HashMap::new,
+        |mut acc, (name, workflow)| {
+            acc.insert(name, workflow);
             acc
         },
     )(input)
 }
 
-fn parse_part(input: &str) -> IResult<&str, Parts> {
+fn parse_part(input: &str) -> IResult<&str, Part> {
     let (input, (x, m, a, s)) = delimited(
         tag("{"),
         tuple((
@@ -195,104 +348,153 @@ fn parse_part(input: &str) -> IResult<&str, Parts> {
         tag("}"),
     )(input)?;
 
-    Ok((input, Parts { x, m, a, s }))
+    Ok((input, [x, m, a, s]))
 }
 
-fn parse_parts(input: &str) -> IResult<&str, Vec<Parts>> {
+fn parse_parts(input: &str) -> IResult<&str, Vec<Part>> {
     separated_list1(line_ending, parse_part)(input)
 }
 
-fn parse(input: &str) -> IResult<&str, (Workflows, Vec<Parts>)> {
-    separated_pair(parse_workflows, line_ending, parse_parts)(input)
+fn parse_system(input: &str) -> IResult<&str, System> {
+    let (input, (workflows, parts)) =
+        separated_pair(parse_workflows, line_ending, parse_parts)(input)?;
+    Ok((input, System { workflows, parts }))
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (_, (workflows, parts)) = parse(input).unwrap();
-    let res = parts
-        .iter()
-        .filter(|p| workflows.run(p))
-        .flat_map(|p| [p.x, p.m, p.a, p.s].into_iter())
-        .sum::<u64>();
-    Some(res)
+/// The system with its workflows compiled once, for both parts.
+pub struct Sorting {
+    system: System,
+    tree: Result<Node, CompileError>,
 }
 
-#[derive(Debug, Clone)]
-struct PartsRange {
-    x: RangeInclusive<u64>,
-    m: RangeInclusive<u64>,
-    a: RangeInclusive<u64>,
-    s: RangeInclusive<u64>,
-}
+pub struct Aplenty;
 
-impl PartsRange {
-    fn set(&mut self, field: char, value: RangeInclusive<u64>) {
-        match field {
-            'x' => self.x = value,
-            'm' => self.m = value,
-            'a' => self.a = value,
-            's' => self.s = value,
-            _ => unreachable!(),
-        }
+impl Solution for Aplenty {
+    const DAY: Day = day!(19);
+    const TITLE: Option<&'static str> = Some("Aplenty");
+
+    type Input = Sorting;
+    type PartOne = Result<u64, CompileError>;
+    type PartTwo = Result<u64, CompileError>;
+
+    fn parse(input: &str) -> Sorting {
+        let system = parse_system(input).unwrap().1;
+        let tree = compile(&system.workflows);
+        Sorting { system, tree }
     }
-}
 
-fn process(part: PartsRange, workflows: &Workflows, target: &Action) -> u64 {
-    match target {
-        Action::Workflow(_) => {
-            let current_workflow = workflows.0.get(target).expect("incorrect target");
-            let mut curr = part;
-            let mut sum = 0;
-            for cond in current_workflow.1.iter() {
-                match cond.test_range(&curr) {
-                    ConditionRangeResult::Next(action) => {
-                        sum += process(curr.clone(), workflows, &action);
-                        break;
-                    }
-                    ConditionRangeResult::Split((passed, action), failed) => {
-                        sum += process(passed, workflows, &action);
-                        curr = failed;
-                    }
-                    ConditionRangeResult::Reject => {}
-                }
-            }
-            sum
+    fn part_one(input: &Sorting, _ctx: &Context) -> Self::PartOne {
+        let tree = input.tree.as_ref().map_err(Clone::clone)?;
+        Ok(input
+            .system
+            .parts
+            .iter()
+            .filter(|part| tree.accepts(part))
+            .flatten()
+            .sum())
+    }
+
+    fn part_two(input: &Sorting, _ctx: &Context) -> Self::PartTwo {
+        let tree = input.tree.as_ref().map_err(Clone::clone)?;
+        Ok(tree.regions().iter().map(volume).sum())
+    }
+
+    /// Writes the workflow graph to the file named by the `dot` parameter, and the accepted
+    /// regions to the one named by `regions`, as JSON if it ends in `.json` and as CSV
+    /// otherwise.
+    fn export(input: &Sorting, ctx: &Context) -> Result<(), String> {
+        let write = |path: &str, content: String| {
+            fs::write(path, content).map_err(|err| format!("cannot write \"{path}\": {err}"))
+        };
+
+        if let Some(path) = ctx.param::<String>("dot") {
+            write(&path, input.system.to_dot())?;
         }
-        Action::Accept => {
-            println!("{:?}", part);
-            (part.x.end() - part.x.start() + 1)
-                * (part.m.end() - part.m.start() + 1)
-                * (part.a.end() - part.a.start() + 1)
-                * (part.s.end() - part.s.start() + 1)
+        if let Some(path) = ctx.param::<String>("regions") {
+            let tree = input.tree.as_ref().map_err(ToString::to_string)?;
+            let regions = tree.regions();
+            let content = if path.ends_with(".json") {
+                regions_json(&regions)
+            } else {
+                regions_csv(&regions)
+            };
+            write(&path, content)?;
         }
-        Action::Reject => 0,
+        Ok(())
     }
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (_, (workflows, _)) = parse(input).unwrap();
-    let part = PartsRange {
-        x: 1..=4000,
-        m: 1..=4000,
-        a: 1..=4000,
-        s: 1..=4000,
-    };
-    let res = process(part, &workflows, &Action::Workflow("in".to_string()));
-    Some(res)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(19114));
+        let result = Aplenty::solve_part_one(
+            &advent_of_code::template::read_file("examples", DAY).unwrap(),
+            &Context::example(),
+        );
+        assert_eq!(result, Ok(19114));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(167409079868000));
+        let result = Aplenty::solve_part_two(
+            &advent_of_code::template::read_file("examples", DAY).unwrap(),
+            &Context::example(),
+        );
+        assert_eq!(result, Ok(167409079868000));
+    }
+
+    #[test]
+    fn test_regions() {
+        let input = Aplenty::parse("in{x<11:A,m>20:R,A}\n\n{x=1,m=2,a=3,s=4}");
+        let regions = input.tree.unwrap().regions();
+        assert_eq!(
+            regions,
+            vec![
+                [(1, 11), (1, 4001), (1, 4001), (1, 4001)],
+                [(11, 4001), (1, 21), (1, 4001), (1, 4001)],
+            ]
+        );
+        assert_eq!(
+            regions_csv(&regions),
+            "x_min,x_max,m_min,m_max,a_min,a_max,s_min,s_max\n\
+             1,10,1,4000,1,4000,1,4000\n\
+             11,4000,1,20,1,4000,1,4000\n"
+        );
+        assert_eq!(
+            regions_json(&regions[..1]),
+            "[\n  {\"x\": [1, 10], \"m\": [1, 4000], \"a\": [1, 4000], \"s\": [1, 4000]}\n]\n"
+        );
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        let input = Aplenty::parse("in{x<5:ab,A}\nab{m>3:in,R}\n\n{x=1,m=2,a=3,s=4}");
+        assert_eq!(
+            input.tree,
+            Err(CompileError::Cycle(vec![
+                "in".to_string(),
+                "ab".to_string(),
+                "in".to_string()
+            ]))
+        );
+
+        let input = Aplenty::parse("in{x<5:ab,A}\n\n{x=1,m=2,a=3,s=4}");
+        assert_eq!(
+            input.tree.unwrap_err().to_string(),
+            "unknown workflow \"ab\""
+        );
+    }
+
+    #[test]
+    fn test_dot() {
+        let input = Aplenty::parse(&advent_of_code::template::read_file("examples", DAY).unwrap());
+        let dot = input.system.to_dot();
+        assert!(dot.starts_with("digraph workflows {"));
+        assert!(dot.contains("    in [shape=box, style=filled"));
+        assert!(dot.contains("    px -> qkq [label=\"a<2006\"];\n"));
+        assert!(dot.contains("    px -> rfg [label=\"else\", style=dashed];\n"));
     }
 }
@@ -1,19 +1,22 @@
 use std::{
-    collections::{HashMap, HashSet},
-    ops::Not,
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::Display,
+    fs,
 };
 
-use itertools::Itertools;
+use advent_of_code::{
+    math::lcm_all,
+    parse::{key_list, lines},
+    template::Context,
+};
 use nom::{
-    bytes::complete::tag,
-    character::complete::{self, alpha1, digit1, line_ending, one_of, space0},
-    combinator::{complete, opt},
-    multi::{fold_many1, many1, separated_list1},
-    sequence::{delimited, preceded, separated_pair, terminated, tuple, Tuple},
+    character::complete::{alpha1, one_of},
+    combinator::opt,
+    sequence::tuple,
     IResult,
 };
 
-advent_of_code::solution!(20);
+advent_of_code::solution!(20, export = export_dot);
 
 #[derive(Debug, Clone, Copy)]
 enum Signal {
@@ -99,7 +102,7 @@ impl Network {
     }
 
     fn push_button(&mut self) -> (u64, u64) {
-        let (mut low_count, mut high_count, mut is_reached) = (1, 0, false);
+        let (mut low_count, mut high_count) = (1, 0);
 
         let mut targets = vec!["broadcaster".to_string()];
 
@@ -116,64 +119,219 @@ impl Network {
 
             for reciever in recievers {
                 let target = self.0.get_mut(&reciever).expect("wrong target");
-                // println!("{}: {:?} -> {:?}", source_name, signal, target);
-
                 if target.recieve(signal, &source_name) {
                     targets.push(reciever);
                 }
             }
-            // println!("{:?}", targets);
         }
 
         (low_count, high_count)
     }
 
-    fn push_button_with_search(&mut self, nodes: &[String]) -> Vec<String> {
-        let mut nodes_to_find: HashSet<String> = HashSet::from_iter(nodes.iter().cloned());
-        let mut targets = vec!["broadcaster".to_string()];
+    /// Pushes the button once, returning the pulses sent to `watched` and their senders.
+    fn push_button_watching(&mut self, watched: &str) -> Vec<(String, Signal)> {
+        let mut pulses = vec![];
+        let mut targets = VecDeque::from(["broadcaster".to_string()]);
 
-        while !targets.is_empty() {
-            let source_name = targets.remove(0);
+        while let Some(source_name) = targets.pop_front() {
             let source = self.0.get(&source_name).expect("wrong source");
             let signal = source.signal_to_send();
             let recievers = source.recievers.clone();
 
             for reciever in recievers {
+                if reciever == watched {
+                    pulses.push((source_name.clone(), signal));
+                }
                 let target = self.0.get_mut(&reciever).expect("wrong target");
-                // println!("{}: {:?} -> {:?}", source_name, signal, target);
-
                 if target.recieve(signal, &source_name) {
-                    targets.push(reciever);
+                    targets.push_back(reciever);
+                }
+            }
+        }
+
+        pulses
+    }
+
+    /// The modules sending pulses to `name`.
+    fn feeders(&self, name: &str) -> Vec<&Module> {
+        let mut feeders: Vec<_> = self
+            .0
+            .values()
+            .filter(|module| module.recievers.iter().any(|r| r == name))
+            .collect();
+        feeders.sort_by(|a, b| a.name.cmp(&b.name));
+        feeders
+    }
+
+    /// All modules `name` depends on, i.e. it can be reached from, except the broadcaster.
+    fn upstream(&self, name: &str) -> HashSet<String> {
+        let mut seen = HashSet::from([name.to_string()]);
+        let mut queue = vec![name];
+        while let Some(current) = queue.pop() {
+            for feeder in self.feeders(current) {
+                if feeder.mod_type != ModuleType::Broadcaster && seen.insert(feeder.name.clone()) {
+                    queue.push(&feeder.name);
                 }
+            }
+        }
+        seen
+    }
 
-                if matches!(signal, Signal::Low) && nodes.contains(&target.name) {
-                    nodes_to_find.remove(&target.name);
+    /// The module graph in Graphviz DOT format, e.g. for `dot -Tsvg`.
+    fn to_dot(&self) -> String {
+        let mut names: Vec<&String> = self.0.keys().collect();
+        names.sort();
+
+        let mut dot = String::from("digraph network {\n    rankdir=LR;\n");
+        for name in &names {
+            let style = match self.0[*name].mod_type {
+                ModuleType::Broadcaster => "shape=box, style=filled, fillcolor=lightblue",
+                ModuleType::Flipper(_) => "shape=ellipse, label=\"%\\N\"",
+                ModuleType::Conjuction(_) => {
+                    "shape=diamond, style=filled, fillcolor=lightyellow, label=\"&\\N\""
                 }
+                ModuleType::None => "shape=doublecircle, style=filled, fillcolor=lightpink",
+            };
+            dot.push_str(&format!("    {name} [{style}];\n"));
+        }
+        for name in &names {
+            for reciever in &self.0[*name].recievers {
+                dot.push_str(&format!("    {name} -> {reciever};\n"));
             }
-            // println!("{:?}", targets);
         }
+        dot.push_str("}\n");
+        dot
+    }
+}
 
-        nodes_to_find.into_iter().collect()
+/// Why the sub-circuit analysis of part two does not apply to a network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitError {
+    /// `rx` must be fed by exactly one module, these are the ones found.
+    Feeders(Vec<String>),
+    /// The module feeding `rx` is not a conjunction.
+    NotConjunction(String),
+    /// Two inputs of the final conjunction share upstream modules, so they do not cycle
+    /// independently.
+    Shared(String, String),
+    /// An input did not send a high pulse within the given number of presses.
+    NoCycle(String, u64),
+    /// An input first fired after `start` presses, but then repeated every `length`, so
+    /// the cycles are offset and a plain least common multiple is wrong.
+    Offset {
+        input: String,
+        start: u64,
+        length: u64,
+    },
+}
+
+impl Display for CircuitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CircuitError::Feeders(feeders) => write!(
+                f,
+                "rx must be fed by exactly one module, found {}",
+                if feeders.is_empty() {
+                    "none".to_string()
+                } else {
+                    feeders.join(", ")
+                }
+            ),
+            CircuitError::NotConjunction(name) => {
+                write!(f, "rx is fed by {name}, which is not a conjunction")
+            }
+            CircuitError::Shared(a, b) => write!(
+                f,
+                "the sub-circuits of {a} and {b} share modules and do not cycle independently"
+            ),
+            CircuitError::NoCycle(name, presses) => {
+                write!(f, "{name} sent no high pulse within {presses} presses")
+            }
+            CircuitError::Offset {
+                input,
+                start,
+                length,
+            } => write!(
+                f,
+                "{input} first fires after {start} presses but repeats every {length}"
+            ),
+        }
     }
+}
 
-    fn state(&self) -> Vec<Module> {
-        self.0
-            .values()
-            .cloned()
-            .sorted_by_key(|m| m.name.to_string())
-            .collect()
+impl std::error::Error for CircuitError {}
+
+/// Presses after which part two gives up on an input ever firing.
+const MAX_PRESSES: u64 = 1 << 16;
+
+/// The number of presses until `rx` gets a low pulse.
+///
+/// `rx` is expected to be fed by a single conjunction, whose inputs are each driven by a
+/// separate sub-circuit firing a high pulse every `n` presses. The conjunction sends the low
+/// pulse once all of them fire in the same press, after the least common multiple of their
+/// cycle lengths.
+fn presses_until_rx(mut network: Network) -> Result<u64, CircuitError> {
+    let hub = match network.feeders("rx")[..] {
+        [hub] if matches!(hub.mod_type, ModuleType::Conjuction(_)) => hub.name.clone(),
+        [hub] => return Err(CircuitError::NotConjunction(hub.name.clone())),
+        ref feeders => {
+            return Err(CircuitError::Feeders(
+                feeders.iter().map(|m| m.name.clone()).collect(),
+            ))
+        }
+    };
+
+    let inputs = network.0[&hub].inputs.clone();
+    let circuits: Vec<_> = inputs.iter().map(|input| network.upstream(input)).collect();
+    for (i, a) in circuits.iter().enumerate() {
+        for (j, b) in circuits.iter().enumerate().skip(i + 1) {
+            if !a.is_disjoint(b) {
+                return Err(CircuitError::Shared(inputs[i].clone(), inputs[j].clone()));
+            }
+        }
     }
+
+    // the first two presses each input fires in
+    let mut fired: HashMap<String, Vec<u64>> = HashMap::new();
+    for press in 1..=MAX_PRESSES {
+        for (sender, signal) in network.push_button_watching(&hub) {
+            if matches!(signal, Signal::Low) {
+                continue;
+            }
+            let presses = fired.entry(sender).or_default();
+            if presses.len() < 2 && presses.last() != Some(&press) {
+                presses.push(press);
+            }
+        }
+        if inputs
+            .iter()
+            .all(|input| fired.get(input).is_some_and(|p| p.len() == 2))
+        {
+            break;
+        }
+    }
+
+    let mut lengths = vec![];
+    for input in &inputs {
+        match fired.get(input).map(Vec::as_slice) {
+            Some(&[start, second]) if second - start == start => lengths.push(start),
+            Some(&[start, second]) => {
+                return Err(CircuitError::Offset {
+                    input: input.clone(),
+                    start,
+                    length: second - start,
+                })
+            }
+            _ => return Err(CircuitError::NoCycle(input.clone(), MAX_PRESSES)),
+        }
+    }
+
+    Ok(lcm_all(&lengths).expect("cycle lengths overflow"))
 }
 
 fn parse_module(input: &str) -> IResult<&str, Module> {
-    let (input, (mod_type, name, recievers)) = tuple((
-        opt(one_of("&%")),
-        alpha1,
-        preceded(
-            tuple((space0, tag("->"), space0)),
-            separated_list1(tuple((tag(","), space0)), alpha1),
-        ),
-    ))(input)?;
+    let (input, ((mod_type, name), recievers)) =
+        key_list(tuple((opt(one_of("&%")), alpha1)), "->", alpha1)(input)?;
 
     let mod_type = match mod_type {
         Some('&') => ModuleType::Conjuction(0),
@@ -191,7 +349,7 @@ fn parse_module(input: &str) -> IResult<&str, Module> {
 }
 
 fn parse(input: &str) -> IResult<&str, Network> {
-    let (input, mut modules) = separated_list1(line_ending, parse_module)(input)?;
+    let (input, mut modules) = lines(parse_module)(input)?;
 
     let mut modules_set: HashSet<String> = modules.iter().map(|el| el.name.clone()).collect();
     let mut inputs_map =
@@ -232,55 +390,19 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(low_count * high_count)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (_, mut modules) = parse(input).unwrap();
-
-    let fin = "rx".to_string();
-
-    let node = modules
-        .0
-        .iter()
-        .find_map(|(_, v)| v.recievers.contains(&fin).then_some(v.name.clone()))
-        .expect("no node found");
-
-    let mut nodes: Vec<String> = modules
-        .0
-        .iter()
-        .filter_map(|(_, v)| v.recievers.contains(&node).then_some(v.name.clone()))
-        .collect();
-
-    println!("{:?}", nodes);
-
-    let mut indexes = vec![];
-    for i in 0.. {
-        let len_before = nodes.len();
-        nodes = modules.push_button_with_search(&nodes);
-        if len_before != nodes.len() {
-            indexes.push(i + 1);
-        }
-        if nodes.is_empty() {
-            break;
-        };
-    }
-    println!("{:?}", indexes);
-
-    Some(lcm(&indexes))
-}
-
-pub fn lcm(nums: &[u64]) -> u64 {
-    if nums.len() == 1 {
-        return nums[0];
-    }
-    let a = nums[0];
-    let b = lcm(&nums[1..]);
-    a * b / gcd_of_two_numbers(a, b)
+pub fn part_two(input: &str) -> Result<u64, CircuitError> {
+    let (_, network) = parse(input).unwrap();
+    presses_until_rx(network)
 }
 
-fn gcd_of_two_numbers(a: u64, b: u64) -> u64 {
-    if b == 0 {
-        return a;
-    }
-    gcd_of_two_numbers(b, a % b)
+/// Writes the module graph to the file named by the `dot` parameter, e.g.
+/// `cargo solve 20 --param dot=network.dot`.
+fn export_dot(input: &str, ctx: &Context) -> Result<(), String> {
+    let Some(path) = ctx.param::<String>("dot") else {
+        return Ok(());
+    };
+    let (_, network) = parse(input).unwrap();
+    fs::write(&path, network.to_dot()).map_err(|err| format!("cannot write \"{path}\": {err}"))
 }
 
 #[cfg(test)]
@@ -289,23 +411,78 @@ mod tests {
 
     #[test]
     fn test_part_one_1() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
+        let result =
+            part_one(&advent_of_code::template::read_file_part("examples", DAY, 1).unwrap());
         assert_eq!(result, Some(32000000));
     }
 
     #[test]
     fn test_part_one_2() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
+        let result =
+            part_one(&advent_of_code::template::read_file_part("examples", DAY, 2).unwrap());
         assert_eq!(result, Some(11687500));
     }
 
+    /// Two counters firing every 3 and 7 presses, joined by `hub` in front of `rx`.
+    const COUNTERS: &str = "\
+broadcaster -> ax, bx
+%ax -> ay, ca
+%ay -> ca
+&ca -> ax, ia
+&ia -> hub
+%bx -> by, cb
+%by -> bz, cb
+%bz -> cb
+&cb -> bx, ib
+&ib -> hub
+&hub -> rx";
+
+    /// Presses until `rx` gets a low pulse, by pressing.
+    fn brute_force(input: &str) -> u64 {
+        let (_, mut network) = parse(input).unwrap();
+        (1..)
+            .find(|_| {
+                network
+                    .push_button_watching("rx")
+                    .iter()
+                    .any(|(_, signal)| matches!(signal, Signal::Low))
+            })
+            .unwrap()
+    }
+
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_two(COUNTERS);
+        assert_eq!(result, Ok(21));
+        assert_eq!(brute_force(COUNTERS), 21);
+    }
+
+    #[test]
+    fn test_part_two_assumptions() {
+        let input = advent_of_code::template::read_file_part("examples", DAY, 1).unwrap();
+        let result = part_two(&input);
+        assert_eq!(result, Err(CircuitError::Feeders(vec![])));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "rx must be fed by exactly one module, found none"
+        );
+
+        let shared = COUNTERS.replace("&ib -> hub", "&ib -> hub, ay");
+        assert_eq!(
+            part_two(&shared),
+            Err(CircuitError::Shared("ia".to_string(), "ib".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_dot() {
+        let (_, network) = parse(COUNTERS).unwrap();
+        let dot = network.to_dot();
+        assert!(dot.starts_with("digraph network {"));
+        assert!(dot.contains("    broadcaster [shape=box"));
+        assert!(dot.contains("    ax [shape=ellipse"));
+        assert!(dot.contains("    hub [shape=diamond"));
+        assert!(dot.contains("    rx [shape=doublecircle"));
+        assert!(dot.contains("    hub -> rx;\n"));
     }
 }
@@ -1,26 +1,19 @@
-use std::collections::{HashMap, HashSet, VecDeque};
-
-use glam::IVec2;
-use nom::{
-    bytes::complete::tag,
-    character::complete::{self, alpha1, digit1, line_ending, one_of, space0},
-    combinator::{complete, map_res, opt},
-    multi::{fold_many1, many1, separated_list1},
-    sequence::{delimited, preceded, separated_pair, terminated, tuple, Tuple},
-    IResult,
+use std::collections::{HashMap, HashSet};
+
+use advent_of_code::{
+    direction::{Direction, Pos},
+    grid::Grid,
+    search::distances,
+    template::Context,
+    visualize::{Color, Frame, Visualizer},
 };
-use pathfinding::{grid, num_traits::Pow};
 
 advent_of_code::solution!(21);
 
-const DOWN: IVec2 = IVec2::new(1, 0);
-const UP: IVec2 = IVec2::new(-1, 0);
-const LEFT: IVec2 = IVec2::new(0, -1);
-const RIGHT: IVec2 = IVec2::new(0, 1);
-
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Cell {
     Garden,
-    Position,
+    Start,
     Rock,
 }
 
@@ -30,140 +23,189 @@ impl TryFrom<char> for Cell {
         match value {
             '#' => Ok(Cell::Rock),
             '.' => Ok(Cell::Garden),
-            'S' => Ok(Cell::Position),
+            'S' => Ok(Cell::Start),
             _ => Err(()),
         }
     }
 }
 
-// impl From<char> for Cell {
-//     fn from(value: char) -> Self {
-//         match value {
-//             '#' => Cell::Rock,
-//             '.' => Cell::Garden,
-//             'S' => Cell::Position,
-//             _ => panic!("Invalid cell"),
-//         }
-//     }
-// }
-
-fn parse(input: &str) -> IResult<&str, Vec<Vec<Cell>>> {
-    separated_list1(line_ending, many1(map_res(one_of(".#S"), Cell::try_from)))(input)
+fn parse(input: &str) -> (Grid<Cell>, Pos) {
+    let grid = Grid::parse(input, |c| Cell::try_from(c).ok()).expect("invalid map");
+    let start = grid.find(&Cell::Start).expect("No start position");
+    (grid, start)
 }
 
-fn walk(grid: &[Vec<Cell>], start: IVec2, steps_count: i32) -> i32 {
-    let size = (grid.len() as i32 - 1, grid[0].len() as i32 - 1);
-    let mut current_positions = HashSet::from([start]);
-
-    for step in 1..=steps_count {
-        let mut new_positions = HashSet::new();
-
-        for pos in current_positions {
-            for dir in [DOWN, UP, LEFT, RIGHT] {
-                let new_pos = pos + dir;
-                if new_pos.x.clamp(0, size.0) != new_pos.x
-                    || new_pos.y.clamp(0, size.1) != new_pos.y
-                    || matches!(grid[new_pos.x as usize][new_pos.y as usize], Cell::Rock)
-                {
-                    continue;
-                }
-                // println!("{} -> {}", pos, new_pos);
-                new_positions.insert(new_pos);
-            }
+/// The garden plots the elf can end up on after exactly `steps` steps.
+///
+/// Every plot reachable in `d <= steps` steps with the same parity as `steps` counts, as the
+/// elf can always step back and forth to waste the remaining steps.
+fn walk(grid: &Grid<Cell>, start: Pos, steps: usize, vis: &mut Visualizer) -> usize {
+    let successors = |&pos: &Pos| {
+        grid.neighbours_4(pos)
+            .filter(|&(_, &cell)| cell != Cell::Rock)
+            .map(|(next, _)| next)
+    };
+    let dist = distances([start], successors, steps);
+
+    if vis.is_enabled() {
+        for step in 0..=steps {
+            vis.frame(|| frame(grid, &dist, step));
         }
-
-        current_positions = new_positions;
     }
-    current_positions.len() as i32
-}
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (_, grid) = parse(input).ok()?;
+    dist.into_values().filter(|d| d % 2 == steps % 2).count()
+}
 
-    let start = grid
-        .iter()
-        .enumerate()
-        .find_map(|(x, row)| {
-            row.iter()
-                .position(|cell| matches!(cell, Cell::Position))
-                .map(|y| IVec2::new(x as i32, y as i32))
-        })
-        .expect("No start position");
+/// The map with the plots the elf can be on after `step` steps marked.
+fn frame(grid: &Grid<Cell>, dist: &HashMap<Pos, usize>, step: usize) -> Frame {
+    let mut frame = Frame::from_grid(grid, |cell| match cell {
+        Cell::Rock => ('#', Color::Gray),
+        _ => ('.', Color::Green),
+    });
+    for (&pos, &d) in dist {
+        if d <= step && d % 2 == step % 2 {
+            frame.set(pos, 'O', Color::Yellow);
+        }
+    }
+    frame.with_caption(format!("step {step}"))
+}
 
-    Some(walk(&grid, start, 6) as u32)
+/// A breadth-first walk on the map repeating in every direction, one step at a time.
+struct InfiniteWalk<'a> {
+    grid: &'a Grid<Cell>,
+    seen: HashSet<Pos>,
+    frontier: Vec<Pos>,
+    /// `reachable[i]` is the number of plots reachable in exactly `i` steps.
+    reachable: Vec<u64>,
 }
 
-fn walk_infinitely(grid: &[Vec<Cell>], start: IVec2, steps_count: i32) -> u64 {
-    let size = IVec2::new(grid.len() as i32, grid[0].len() as i32);
-    let half = size.x / 2;
-    let mut current_positions = HashSet::from([start]);
-    let mut results = Vec::new();
+impl<'a> InfiniteWalk<'a> {
+    fn new(grid: &'a Grid<Cell>, start: Pos) -> Self {
+        InfiniteWalk {
+            grid,
+            seen: HashSet::from([start]),
+            frontier: vec![start],
+            reachable: vec![1],
+        }
+    }
 
-    for step in 1.. {
-        let mut new_positions = HashSet::new();
+    fn reachable(&mut self, steps: usize) -> u64 {
+        while self.reachable.len() <= steps {
+            self.step();
+        }
+        self.reachable[steps]
+    }
 
-        for pos in current_positions {
-            for dir in [DOWN, UP, LEFT, RIGHT] {
-                let new_pos = (pos + dir).rem_euclid(size);
-                if matches!(grid[new_pos.x as usize][new_pos.y as usize], Cell::Rock) {
-                    continue;
+    fn step(&mut self) {
+        let mut next = vec![];
+        for pos in &self.frontier {
+            for dir in Direction::ALL {
+                let new_pos = *pos + dir;
+                if *self.grid.get_wrapping(new_pos) != Cell::Rock && self.seen.insert(new_pos) {
+                    next.push(new_pos);
                 }
-                // println!("{} -> {}", pos, new_pos);
-                new_positions.insert(new_pos);
             }
         }
+        self.frontier = next;
+
+        // plots first reached two steps ago can still be reached by stepping back and forth
+        let steps = self.reachable.len();
+        let before = if steps >= 2 {
+            self.reachable[steps - 2]
+        } else {
+            0
+        };
+        self.reachable.push(before + self.frontier.len() as u64);
+    }
+}
 
-        current_positions = new_positions;
-
-        if step == half + size.x * results.len() as i32 {
-            println!("Add: {}", current_positions.len());
-            results.push(current_positions.len() as i32);
-
-            if results.len() == 3 {
-                let (d0, d1, d2) = (
-                    results[0] as u64,
-                    (results[1] - results[0]) as u64,
-                    (results[2] - results[1]) as u64,
-                );
-
-                let div = (steps_count / size.x) as u64;
-                return d0 + d1 * div + (div * (div - 1) / 2) * (d2 - d1);
-            }
+/// The garden plots reachable after exactly `steps` steps on the infinite map.
+///
+/// Once the walk has spread over a few copies of the map, the counts after `r`, `r + n`,
+/// `r + 2n`, ... steps grow quadratically, `n` being the size of the map. The walk is
+/// simulated until the second differences of that sequence settle, the rest is extrapolated.
+fn walk_infinitely(grid: &Grid<Cell>, start: Pos, steps: usize) -> u64 {
+    assert_eq!(grid.width(), grid.height(), "the map must be square");
+    let period = grid.width();
+    let mut walk = InfiniteWalk::new(grid, start);
+
+    let mut from = steps % period;
+    while from + 4 * period < steps {
+        let counts: Vec<i64> = (0..5)
+            .map(|i| walk.reachable(from + i * period) as i64)
+            .collect();
+        let diffs: Vec<i64> = counts.windows(2).map(|w| w[1] - w[0]).collect();
+        let second: Vec<i64> = diffs.windows(2).map(|w| w[1] - w[0]).collect();
+
+        if second.iter().all(|&d| d == second[0]) {
+            let n = ((steps - from) / period) as i64;
+            return (counts[0] + n * diffs[0] + n * (n - 1) / 2 * second[0]) as u64;
         }
+        from += period;
     }
-    0
+    walk.reachable(steps)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (_, grid) = parse(input).ok()?;
-
-    let start = grid
-        .iter()
-        .enumerate()
-        .find_map(|(x, row)| {
-            row.iter()
-                .position(|cell| matches!(cell, Cell::Position))
-                .map(|y| IVec2::new(x as i32, y as i32))
-        })
-        .expect("No start position");
-
-    let steps_count = 26_501_365;
-    Some(walk_infinitely(&grid, start, steps_count))
+/// The elf takes 64 steps, or 6 in the example, unless the `steps` parameter is set.
+pub fn part_one(input: &str, ctx: &Context) -> Option<usize> {
+    let (grid, start) = parse(input);
+    let steps = ctx.param_or("steps", ctx.example_or(6, 64));
+    Some(walk(&grid, start, steps, &mut ctx.visualizer()))
+}
+
+/// The elf takes 26501365 steps, or 5000 in the example, unless the `steps` parameter is set.
+pub fn part_two(input: &str, ctx: &Context) -> Option<u64> {
+    let (grid, start) = parse(input);
+    let steps = ctx.param_or("steps", ctx.example_or(5000, 26_501_365));
+    Some(walk_infinitely(&grid, start, steps))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Moves every elf one step at a time, the obvious but slow way.
+    fn brute_force(grid: &Grid<Cell>, start: Pos, steps: usize) -> u64 {
+        let mut positions = HashSet::from([start]);
+        for _ in 0..steps {
+            positions = positions
+                .iter()
+                .flat_map(|&pos| Direction::ALL.map(|dir| pos + dir))
+                .filter(|&pos| *grid.get_wrapping(pos) != Cell::Rock)
+                .collect();
+        }
+        positions.len() as u64
+    }
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", DAY).unwrap(),
+            &Context::example(),
+        );
         assert_eq!(result, Some(16));
     }
 
+    #[test]
+    fn test_extrapolation_matches_brute_force() {
+        let (grid, start) = parse(&advent_of_code::template::read_file("examples", DAY).unwrap());
+        for steps in [6, 10, 50, 100, 101, 130, 177] {
+            assert_eq!(
+                walk_infinitely(&grid, start, steps),
+                brute_force(&grid, start, steps),
+                "{steps} steps"
+            );
+        }
+    }
+
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let input = advent_of_code::template::read_file("examples", DAY).unwrap();
+        assert_eq!(part_two(&input, &Context::example()), Some(16733044));
+
+        for (steps, plots) in [(500, 167004), (1000, 668697)] {
+            let ctx = Context::example().with_param("steps", steps);
+            assert_eq!(part_two(&input, &ctx), Some(plots));
+        }
     }
 }
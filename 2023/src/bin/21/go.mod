@@ -1,10 +0,0 @@
-module aoc
-
-go 1.21.5
-
-require github.com/pemoreau/advent-of-code/go v0.0.0-20231221181215-88e7b80018b4
-
-require (
-	github.com/oleiade/lane/v2 v2.0.0 // indirect
-	golang.org/x/exp v0.0.0-20231214170342-aacd6d4b4611 // indirect
-)
@@ -1,11 +1,148 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    str::FromStr,
+};
+
+use advent_of_code::parse::integers;
+use glam::IVec3;
+use itertools::iproduct;
+
 advent_of_code::solution!(22);
 
-pub fn part_one(input: &str) -> Option<u32> {
-    None
+/// An axis-aligned box of cubes, both corners included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Brick {
+    min: IVec3,
+    max: IVec3,
+}
+
+impl FromStr for Brick {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match integers(s)[..] {
+            [x1, y1, z1, x2, y2, z2] => {
+                let a = IVec3::new(x1 as i32, y1 as i32, z1 as i32);
+                let b = IVec3::new(x2 as i32, y2 as i32, z2 as i32);
+                Ok(Brick {
+                    min: a.min(b),
+                    max: a.max(b),
+                })
+            }
+            _ => Err(format!("invalid brick: {s}")),
+        }
+    }
+}
+
+impl Brick {
+    /// The columns the brick occupies when seen from above.
+    fn footprint(&self) -> impl Iterator<Item = (i32, i32)> {
+        iproduct!(self.min.x..=self.max.x, self.min.y..=self.max.y)
+    }
+
+    /// The same brick, moved so its bottom is at height `z`.
+    fn dropped_to(&self, z: i32) -> Brick {
+        let offset = IVec3::new(0, 0, z - self.min.z);
+        Brick {
+            min: self.min + offset,
+            max: self.max + offset,
+        }
+    }
+}
+
+/// The bricks after they have fallen, by index in falling order.
+struct Stack {
+    /// `supports[i]` are the bricks resting directly on brick `i`.
+    supports: Vec<Vec<usize>>,
+    /// `supported_by[i]` are the bricks brick `i` rests on directly.
+    supported_by: Vec<Vec<usize>>,
+}
+
+impl Stack {
+    /// Lets the bricks fall, lowest first, until they rest on the ground or another brick.
+    fn settle(mut bricks: Vec<Brick>) -> Stack {
+        bricks.sort_by_key(|brick| brick.min.z);
+
+        // the highest cube of every column and the brick it belongs to
+        let mut tops: HashMap<(i32, i32), (i32, usize)> = HashMap::new();
+        let mut supports = vec![vec![]; bricks.len()];
+        let mut supported_by = vec![vec![]; bricks.len()];
+
+        for (i, brick) in bricks.iter().enumerate() {
+            let below: Vec<_> = brick.footprint().filter_map(|col| tops.get(&col)).collect();
+            let floor = below.iter().map(|&&(z, _)| z).max().unwrap_or(0);
+
+            let mut under: Vec<_> = below
+                .iter()
+                .filter(|&&&(z, _)| z == floor)
+                .map(|&&(_, j)| j)
+                .collect();
+            under.sort_unstable();
+            under.dedup();
+            for &j in &under {
+                supports[j].push(i);
+            }
+            supported_by[i] = under;
+
+            let settled = brick.dropped_to(floor + 1);
+            for col in settled.footprint() {
+                tops.insert(col, (settled.max.z, i));
+            }
+        }
+
+        Stack {
+            supports,
+            supported_by,
+        }
+    }
+
+    /// Whether brick `i` can be removed without any other brick falling.
+    fn is_removable(&self, i: usize) -> bool {
+        self.supports[i]
+            .iter()
+            .all(|&j| self.supported_by[j].len() > 1)
+    }
+
+    /// The number of other bricks that fall if brick `i` is removed.
+    fn chain_reaction(&self, i: usize) -> usize {
+        let mut missing = vec![0; self.supports.len()];
+        let mut queue = VecDeque::from([i]);
+        let mut fallen = 0;
+
+        while let Some(gone) = queue.pop_front() {
+            for &j in &self.supports[gone] {
+                missing[j] += 1;
+                if missing[j] == self.supported_by[j].len() {
+                    fallen += 1;
+                    queue.push_back(j);
+                }
+            }
+        }
+        fallen
+    }
+}
+
+fn parse(input: &str) -> Stack {
+    let bricks = input.lines().map(|line| line.parse().unwrap()).collect();
+    Stack::settle(bricks)
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let stack = parse(input);
+    Some(
+        (0..stack.supports.len())
+            .filter(|&i| stack.is_removable(i))
+            .count(),
+    )
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+pub fn part_two(input: &str) -> Option<usize> {
+    let stack = parse(input);
+    Some(
+        (0..stack.supports.len())
+            .map(|i| stack.chain_reaction(i))
+            .sum(),
+    )
 }
 
 #[cfg(test)]
@@ -14,13 +151,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
+        assert_eq!(result, Some(5));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
+        assert_eq!(result, Some(7));
     }
 }
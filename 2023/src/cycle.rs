@@ -0,0 +1,191 @@
+/// Cycle detection for puzzles which ask for the state after an absurd number of steps.
+///
+/// A step function is applied to a state until a state repeats, after which every later step
+/// can be looked up instead of simulated.
+use std::{collections::HashMap, hash::Hash};
+
+/// The states visited by repeatedly applying a step function, up to the first repetition.
+///
+/// `states[i]` is the state after `i` steps. The states from `start` on repeat every
+/// `length` steps.
+#[derive(Debug, Clone)]
+pub struct Cycle<S> {
+    pub start: usize,
+    pub length: usize,
+    states: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    /// The state after `steps` applications of the step function.
+    pub fn state_at(&self, steps: usize) -> &S {
+        &self.states[self.index_of(steps)]
+    }
+
+    /// A value derived from the state after `steps` applications of the step function.
+    pub fn value_at<V>(&self, steps: usize, value: impl FnOnce(&S) -> V) -> V {
+        value(self.state_at(steps))
+    }
+
+    /// All states before the cycle followed by a single round of it.
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+
+    fn index_of(&self, steps: usize) -> usize {
+        if steps < self.start {
+            steps
+        } else {
+            self.start + (steps - self.start) % self.length
+        }
+    }
+}
+
+/// Applies `step` to `initial` until a state repeats, remembering every state by its hash.
+///
+/// Never returns if the states do not repeat.
+///
+/// ```
+/// use advent_of_code::cycle::find_cycle;
+///
+/// // 0, 1, 2, 5, 4, 6, 4, 6, ...
+/// let cycle = find_cycle(0u32, |n| (n * n + 1) % 11);
+/// assert_eq!((cycle.start, cycle.length), (4, 2));
+/// assert_eq!(*cycle.state_at(1_000_000_001), 6);
+/// ```
+pub fn find_cycle<S, F>(initial: S, mut step: F) -> Cycle<S>
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::from([(initial.clone(), 0)]);
+    let mut states = vec![initial];
+
+    loop {
+        let next = step(states.last().unwrap());
+        if let Some(&start) = seen.get(&next) {
+            return Cycle {
+                start,
+                length: states.len() - start,
+                states,
+            };
+        }
+        seen.insert(next.clone(), states.len());
+        states.push(next);
+    }
+}
+
+/// The state after `steps` applications of `step`, skipping ahead once a cycle is found.
+///
+/// Unlike [`find_cycle`] this stops early if `steps` is reached before any state repeats.
+pub fn nth_state<S, F>(initial: S, mut step: F, steps: usize) -> S
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::from([(initial.clone(), 0)]);
+    let mut states = vec![initial];
+
+    while states.len() <= steps {
+        let next = step(states.last().unwrap());
+        if let Some(&start) = seen.get(&next) {
+            let cycle = Cycle {
+                start,
+                length: states.len() - start,
+                states,
+            };
+            let index = cycle.index_of(steps);
+            return cycle.states.into_iter().nth(index).unwrap();
+        }
+        seen.insert(next.clone(), states.len());
+        states.push(next);
+    }
+
+    states.pop().unwrap()
+}
+
+/// Brent's algorithm, returning `(start, length)` of the cycle.
+///
+/// Only keeps two states around, which helps if states are large or cannot be hashed, at the
+/// cost of calling `step` about three times as often as [`find_cycle`].
+pub fn brent<S, F>(initial: S, mut step: F) -> (usize, usize)
+where
+    S: PartialEq + Clone,
+    F: FnMut(&S) -> S,
+{
+    // find the cycle length by letting the hare run ahead in growing powers of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // a hare `length` steps ahead meets the tortoise at the start of the cycle
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    (start, length)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 2 -> ...
+    fn step(n: &u32) -> u32 {
+        if *n == 4 {
+            2
+        } else {
+            n + 1
+        }
+    }
+
+    #[test]
+    fn finds_cycle() {
+        let cycle = find_cycle(0, step);
+        assert_eq!((cycle.start, cycle.length), (2, 3));
+        assert_eq!(cycle.states(), &[0, 1, 2, 3, 4]);
+        assert_eq!(*cycle.state_at(1), 1);
+        assert_eq!(*cycle.state_at(5), 2);
+        assert_eq!(*cycle.state_at(1_000_000_000), 4);
+        assert_eq!(cycle.value_at(7, |n| n * 10), 40);
+    }
+
+    #[test]
+    fn extrapolates_state() {
+        assert_eq!(nth_state(0, step, 0), 0);
+        assert_eq!(nth_state(0, step, 3), 3);
+        assert_eq!(nth_state(0, step, 1_000_000_000), 4);
+        // never repeats, but stops after the requested steps
+        assert_eq!(nth_state(0u64, |n| n + 1, 1000), 1000);
+    }
+
+    #[test]
+    fn brent_agrees() {
+        assert_eq!(brent(0, step), (2, 3));
+        assert_eq!(brent(7u32, |_| 7), (0, 1));
+        for seed in 0..50u64 {
+            let f = |n: &u64| (n * n + 1) % 101;
+            let cycle = find_cycle(seed, f);
+            assert_eq!(brent(seed, f), (cycle.start, cycle.length));
+        }
+    }
+}
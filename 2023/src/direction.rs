@@ -0,0 +1,227 @@
+/// Directions and positions on a two-dimensional map.
+///
+/// All days use the same convention: a [`Pos`] is an [`IVec2`] where `x` is the column,
+/// growing to the right, and `y` is the row, growing downwards. The origin is the top left
+/// character of the input, so [`Direction::Up`] is `(0, -1)`.
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Sub},
+    str::FromStr,
+};
+
+use glam::IVec2;
+
+/// A position on a map, `x` being the column and `y` the row of the input.
+pub type Pos = IVec2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, clockwise starting from [`Direction::Up`].
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// The unit offset of a single step in this direction.
+    pub const fn offset(self) -> IVec2 {
+        match self {
+            Direction::Up => IVec2::new(0, -1),
+            Direction::Right => IVec2::new(1, 0),
+            Direction::Down => IVec2::new(0, 1),
+            Direction::Left => IVec2::new(-1, 0),
+        }
+    }
+
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+        }
+    }
+
+    pub const fn is_horizontal(self) -> bool {
+        matches!(self, Direction::Left | Direction::Right)
+    }
+
+    pub const fn is_vertical(self) -> bool {
+        !self.is_horizontal()
+    }
+}
+
+impl From<Direction> for IVec2 {
+    fn from(dir: Direction) -> Self {
+        dir.offset()
+    }
+}
+
+/// An error which can be returned when converting into a [`Direction`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectionError(String);
+
+impl std::error::Error for DirectionError {}
+
+impl Display for DirectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` is not a direction", self.0)
+    }
+}
+
+impl TryFrom<IVec2> for Direction {
+    type Error = DirectionError;
+
+    /// Converts a unit offset back into its direction.
+    fn try_from(offset: IVec2) -> Result<Self, Self::Error> {
+        Direction::ALL
+            .into_iter()
+            .find(|dir| dir.offset() == offset)
+            .ok_or_else(|| DirectionError(offset.to_string()))
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = DirectionError;
+
+    /// Accepts both `U`/`D`/`L`/`R` and arrows `^`/`v`/`<`/`>`.
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'U' | '^' => Ok(Direction::Up),
+            'R' | '>' => Ok(Direction::Right),
+            'D' | 'v' => Ok(Direction::Down),
+            'L' | '<' => Ok(Direction::Left),
+            c => Err(DirectionError(c.to_string())),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = DirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::try_from(c),
+            _ => Err(DirectionError(s.to_string())),
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        };
+        write!(f, "{c}")
+    }
+}
+
+impl Mul<i32> for Direction {
+    type Output = IVec2;
+
+    fn mul(self, steps: i32) -> Self::Output {
+        self.offset() * steps
+    }
+}
+
+impl Add<Direction> for IVec2 {
+    type Output = IVec2;
+
+    fn add(self, dir: Direction) -> Self::Output {
+        self + dir.offset()
+    }
+}
+
+impl AddAssign<Direction> for IVec2 {
+    fn add_assign(&mut self, dir: Direction) {
+        *self += dir.offset();
+    }
+}
+
+impl Sub<Direction> for IVec2 {
+    type Output = IVec2;
+
+    fn sub(self, dir: Direction) -> Self::Output {
+        self - dir.offset()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use glam::IVec2;
+
+    use super::{Direction, Pos};
+
+    #[test]
+    fn turns() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.opposite());
+            assert_eq!(dir.offset() + dir.opposite().offset(), IVec2::ZERO);
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+    }
+
+    #[test]
+    fn screen_coordinates() {
+        let pos = Pos::new(3, 3);
+        assert_eq!(pos + Direction::Up, Pos::new(3, 2));
+        assert_eq!(pos + Direction::Right, Pos::new(4, 3));
+        assert_eq!(pos + Direction::Down * 2, Pos::new(3, 5));
+        assert_eq!(pos - Direction::Left, Pos::new(4, 3));
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Direction::try_from(IVec2::new(-1, 0)), Ok(Direction::Left));
+        assert!(Direction::try_from(IVec2::new(1, 1)).is_err());
+        assert_eq!(IVec2::from(Direction::Down), IVec2::new(0, 1));
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!("U".parse(), Ok(Direction::Up));
+        assert_eq!("R".parse(), Ok(Direction::Right));
+        assert_eq!('v'.try_into(), Ok(Direction::Down));
+        assert_eq!('<'.try_into(), Ok(Direction::Left));
+        assert!("UD".parse::<Direction>().is_err());
+        assert!(Direction::try_from('x').is_err());
+        assert_eq!(Direction::Up.to_string(), "^");
+    }
+}
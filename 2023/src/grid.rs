@@ -0,0 +1,451 @@
+/// A dense two-dimensional grid, as used by most map-based puzzles.
+///
+/// Cells are addressed by a [`Pos`], following the convention documented in [`crate::direction`].
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use glam::IVec2;
+
+use crate::direction::Pos;
+
+/// Offsets of the four orthogonal neighbours of a cell, in the order of [`Direction::ALL`](crate::direction::Direction::ALL).
+pub const NEIGHBOURS_4: [IVec2; 4] = [
+    IVec2::new(0, -1),
+    IVec2::new(1, 0),
+    IVec2::new(0, 1),
+    IVec2::new(-1, 0),
+];
+
+/// Offsets of the eight neighbours of a cell, including diagonals.
+pub const NEIGHBOURS_8: [IVec2; 8] = [
+    IVec2::new(-1, -1),
+    IVec2::new(0, -1),
+    IVec2::new(1, -1),
+    IVec2::new(1, 0),
+    IVec2::new(1, 1),
+    IVec2::new(0, 1),
+    IVec2::new(-1, 1),
+    IVec2::new(-1, 0),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    Empty,
+    UnknownChar {
+        c: char,
+        pos: Pos,
+    },
+    RaggedRow {
+        row: usize,
+        len: usize,
+        expected: usize,
+    },
+}
+
+impl std::error::Error for GridError {}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "grid input is empty."),
+            GridError::UnknownChar { c, pos } => {
+                write!(f, "unexpected character {c:?} at {pos}.")
+            }
+            GridError::RaggedRow { row, len, expected } => {
+                write!(f, "row {row} has {len} cells, expected {expected}.")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells in row-major order.
+    ///
+    /// # Panics
+    /// If the number of cells is not a multiple of `width`.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "cell count is not a multiple of the width"
+        );
+        let height = cells.len() / width;
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a grid with one line per row, mapping each character to a cell.
+    /// Characters the mapping returns [`None`] for are reported as errors.
+    pub fn parse(
+        input: &str,
+        mut mapping: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, GridError> {
+        let mut width = None;
+        let mut cells = vec![];
+
+        for (y, line) in input.lines().enumerate() {
+            let len = line.chars().count();
+            let expected = *width.get_or_insert(len);
+            if len != expected {
+                return Err(GridError::RaggedRow {
+                    row: y,
+                    len,
+                    expected,
+                });
+            }
+
+            for (x, c) in line.chars().enumerate() {
+                let pos = Pos::new(x as i32, y as i32);
+                cells.push(mapping(c).ok_or(GridError::UnknownChar { c, pos })?);
+            }
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Self::from_vec(width, cells)),
+            _ => Err(GridError::Empty),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Size of the grid as `(width, height)`.
+    pub fn size(&self) -> IVec2 {
+        IVec2::new(self.width as i32, self.height as i32)
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height
+    }
+
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        self.contains(pos)
+            .then(|| pos.y as usize * self.width + pos.x as usize)
+    }
+
+    /// Returns the cell at `pos`, or [`None`] if it lies outside of the grid.
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Maps any position onto the grid, as if the grid was repeated infinitely in every direction.
+    pub fn wrap(&self, pos: Pos) -> Pos {
+        pos.rem_euclid(self.size())
+    }
+
+    /// Returns the cell at `pos` on a grid that repeats infinitely in every direction.
+    pub fn get_wrapping(&self, pos: Pos) -> &T {
+        &self[self.wrap(pos)]
+    }
+
+    /// Iterates over the in-bounds orthogonal neighbours of `pos`.
+    pub fn neighbours_4(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.neighbours(pos, &NEIGHBOURS_4)
+    }
+
+    /// Iterates over the in-bounds neighbours of `pos`, including diagonals.
+    pub fn neighbours_8(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.neighbours(pos, &NEIGHBOURS_8)
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        pos: Pos,
+        offsets: &'static [IVec2],
+    ) -> impl Iterator<Item = (Pos, &'a T)> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&offset| self.get(pos + offset).map(|cell| (pos + offset, cell)))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Iterates over all positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Pos::new((i % width) as i32, (i / width) as i32))
+    }
+
+    /// Iterates over all cells with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Returns the position of the first cell, in row-major order, that matches `predicate`.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find_map(|(pos, cell)| predicate(cell).then_some(pos))
+    }
+
+    /// Creates a new grid by applying `f` to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Returns the position of the first cell equal to `value`.
+    pub fn find(&self, value: &T) -> Option<Pos> {
+        self.position(|cell| cell == value)
+    }
+
+    /// Returns the positions of all cells equal to `value`.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Pos> + 'a {
+        self.iter()
+            .filter_map(move |(pos, cell)| (cell == value).then_some(pos))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self::from_vec(width, vec![fill; width * height])
+    }
+
+    fn from_fn(
+        width: usize,
+        height: usize,
+        f: impl Fn(usize, usize) -> Pos,
+        source: &Self,
+    ) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| source[f(x, y)].clone())
+            .collect();
+        Self::from_vec(width, cells)
+    }
+
+    /// Mirrors the grid along its main diagonal, so rows become columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(
+            self.height,
+            self.width,
+            |x, y| Pos::new(y as i32, x as i32),
+            self,
+        )
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    #[must_use]
+    pub fn rotate_cw(&self) -> Self {
+        let last_row = self.height as i32 - 1;
+        Self::from_fn(
+            self.height,
+            self.width,
+            |x, y| Pos::new(y as i32, last_row - x as i32),
+            self,
+        )
+    }
+
+    /// Rotates the grid by 90 degrees counter-clockwise.
+    #[must_use]
+    pub fn rotate_ccw(&self) -> Self {
+        let last_column = self.width as i32 - 1;
+        Self::from_fn(
+            self.height,
+            self.width,
+            |x, y| Pos::new(last_column - y as i32, x as i32),
+            self,
+        )
+    }
+
+    /// Mirrors the grid left to right.
+    #[must_use]
+    pub fn flip_horizontal(&self) -> Self {
+        let last_column = self.width as i32 - 1;
+        Self::from_fn(
+            self.width,
+            self.height,
+            |x, y| Pos::new(last_column - x as i32, y as i32),
+            self,
+        )
+    }
+
+    /// Mirrors the grid top to bottom.
+    #[must_use]
+    pub fn flip_vertical(&self) -> Self {
+        let last_row = self.height as i32 - 1;
+        Self::from_fn(
+            self.width,
+            self.height,
+            |x, y| Pos::new(x as i32, last_row - y as i32),
+            self,
+        )
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, Some)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {pos} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos} is outside of the grid"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use glam::IVec2;
+
+    use super::{Grid, GridError};
+
+    fn grid() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    #[test]
+    fn parses_with_mapping() {
+        let grid = Grid::parse("#.\n.#", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(grid.size(), IVec2::new(2, 2));
+        assert_eq!(grid[IVec2::new(1, 1)], true);
+        assert_eq!(grid[IVec2::new(1, 0)], false);
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        assert_eq!(
+            Grid::parse("..\n.x", |c| (c == '.').then_some(c)),
+            Err(GridError::UnknownChar {
+                c: 'x',
+                pos: IVec2::new(1, 1)
+            })
+        );
+        assert_eq!(
+            "ab\nc".parse::<Grid<char>>(),
+            Err(GridError::RaggedRow {
+                row: 1,
+                len: 1,
+                expected: 2
+            })
+        );
+        assert_eq!("".parse::<Grid<char>>(), Err(GridError::Empty));
+    }
+
+    #[test]
+    fn bounds_checked_and_wrapping_access() {
+        let grid = grid();
+        assert_eq!(grid.get(IVec2::new(2, 1)), Some(&'f'));
+        assert_eq!(grid.get(IVec2::new(3, 0)), None);
+        assert_eq!(grid.get(IVec2::new(0, -1)), None);
+        assert_eq!(grid.get_wrapping(IVec2::new(3, -1)), &'d');
+        assert_eq!(grid.get_wrapping(IVec2::new(-1, 2)), &'c');
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = grid();
+        let n4: Vec<char> = grid
+            .neighbours_4(IVec2::new(0, 0))
+            .map(|(_, c)| *c)
+            .collect();
+        assert_eq!(n4, vec!['b', 'd']);
+        let n8: Vec<char> = grid
+            .neighbours_8(IVec2::new(1, 0))
+            .map(|(_, c)| *c)
+            .collect();
+        assert_eq!(n8, vec!['c', 'f', 'e', 'd', 'a']);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = grid();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&['a', 'b', 'c'], &['d', 'e', 'f']]
+        );
+        let columns: Vec<String> = grid.columns().map(|c| c.collect()).collect();
+        assert_eq!(columns, vec!["ad", "be", "cf"]);
+    }
+
+    #[test]
+    fn find_values() {
+        let grid: Grid<char> = "a.a\n.a.".parse().unwrap();
+        assert_eq!(grid.find(&'a'), Some(IVec2::new(0, 0)));
+        assert_eq!(grid.find(&'x'), None);
+        assert_eq!(grid.find_all(&'a').count(), 3);
+    }
+
+    #[test]
+    fn transformations() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc\n");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+    }
+}
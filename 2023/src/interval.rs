@@ -0,0 +1,371 @@
+/// Sets of integer ranges and piecewise shifts between them.
+///
+/// Puzzles like the seed almanac of day 05 or the part ratings of day 19 deal with billions
+/// of values, but only a handful of distinct ranges. [`IntervalSet`] and [`RangeMap`] work on
+/// those ranges as a whole. All ranges are half-open, like [`std::ops::Range`].
+use std::{fmt::Display, ops::Range};
+
+/// A set of integers stored as sorted, disjoint and non-adjacent ranges.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    ranges: Vec<Range<i64>>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_range(range: Range<i64>) -> Self {
+        let mut set = Self::new();
+        set.insert(range);
+        set
+    }
+
+    pub fn ranges(&self) -> &[Range<i64>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of integers in the set.
+    pub fn len(&self) -> u64 {
+        self.ranges.iter().map(|r| r.end.abs_diff(r.start)).sum()
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn max(&self) -> Option<i64> {
+        self.ranges.last().map(|r| r.end - 1)
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let idx = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(idx).is_some_and(|r| r.start <= value)
+    }
+
+    /// Adds all values of `range`, merging it with overlapping or adjacent ranges.
+    pub fn insert(&mut self, range: Range<i64>) {
+        if range.is_empty() {
+            return;
+        }
+        // ranges ending before `range` starts stay in front, ranges starting after it ends
+        // stay behind, everything in between is merged.
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        let merged = if first == last {
+            range
+        } else {
+            self.ranges[first].start.min(range.start)..self.ranges[last - 1].end.max(range.end)
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Removes all values of `range`.
+    pub fn remove(&mut self, range: Range<i64>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self.ranges.partition_point(|r| r.end <= range.start);
+        let last = self.ranges.partition_point(|r| r.start < range.end);
+        let rest: Vec<_> = self.ranges[first..last]
+            .iter()
+            .flat_map(|r| [r.start..range.start, range.end..r.end])
+            .filter(|r| !r.is_empty())
+            .collect();
+        self.ranges.splice(first..last, rest);
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut res = self.clone();
+        for r in &other.ranges {
+            res.insert(r.clone());
+        }
+        res
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let (mut i, mut j) = (0, 0);
+        let mut ranges = vec![];
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let overlap = a.start.max(b.start)..a.end.min(b.end);
+            if !overlap.is_empty() {
+                ranges.push(overlap);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut res = self.clone();
+        for r in &other.ranges {
+            res.remove(r.clone());
+        }
+        res
+    }
+
+    /// Splits the set into the values below `at` and the values from `at` upwards.
+    pub fn split_at(&self, at: i64) -> (IntervalSet, IntervalSet) {
+        let (mut below, mut above) = (vec![], vec![]);
+        for r in &self.ranges {
+            if r.end <= at {
+                below.push(r.clone());
+            } else if r.start >= at {
+                above.push(r.clone());
+            } else {
+                below.push(r.start..at);
+                above.push(at..r.end);
+            }
+        }
+        (IntervalSet { ranges: below }, IntervalSet { ranges: above })
+    }
+}
+
+impl From<Range<i64>> for IntervalSet {
+    fn from(range: Range<i64>) -> Self {
+        Self::from_range(range)
+    }
+}
+
+impl FromIterator<Range<i64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Range<i64>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+impl Display for IntervalSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{")?;
+        for (i, r) in self.ranges.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}..{}", r.start, r.end)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// A piecewise shift of the integers: values inside a piece move by its offset, all other
+/// values map to themselves.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeMap {
+    /// Sorted, disjoint pieces. A zero offset still shadows pieces inserted later.
+    pieces: Vec<(Range<i64>, i64)>,
+}
+
+impl RangeMap {
+    /// The identity map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shifts the values of `src` by `offset`.
+    ///
+    /// Values already covered by an earlier piece keep their mapping, so the first matching
+    /// line of an almanac wins.
+    pub fn insert(&mut self, src: Range<i64>, offset: i64) {
+        let mut free = IntervalSet::from_range(src);
+        for (r, _) in &self.pieces {
+            free.remove(r.clone());
+        }
+        self.pieces
+            .extend(free.ranges.into_iter().map(|r| (r, offset)));
+        self.pieces.sort_by_key(|(r, _)| r.start);
+    }
+
+    /// Maps `src..src + len` onto `dest..dest + len`, the line format of the day 05 almanac.
+    pub fn insert_mapping(&mut self, dest: i64, src: i64, len: i64) {
+        self.insert(src..src + len, dest - src);
+    }
+
+    pub fn apply(&self, value: i64) -> i64 {
+        let idx = self.pieces.partition_point(|(r, _)| r.end <= value);
+        match self.pieces.get(idx) {
+            Some((r, offset)) if r.start <= value => value + offset,
+            _ => value,
+        }
+    }
+
+    /// Maps every value of `set` at once.
+    pub fn apply_set(&self, set: &IntervalSet) -> IntervalSet {
+        set.ranges
+            .iter()
+            .flat_map(|range| {
+                self.segments().filter_map(move |(r, offset)| {
+                    let offset = offset.unwrap_or(0);
+                    let overlap = r.start.max(range.start)..r.end.min(range.end);
+                    (!overlap.is_empty()).then(|| overlap.start + offset..overlap.end + offset)
+                })
+            })
+            .collect()
+    }
+
+    /// The map applying `self` first and `next` to its result.
+    ///
+    /// Values covered by a piece of either map stay covered, even where the offsets cancel
+    /// out, so that they keep shadowing pieces inserted later.
+    #[must_use]
+    pub fn then(&self, next: &RangeMap) -> RangeMap {
+        let mut pieces = vec![];
+        for (r, offset) in self.segments() {
+            let shift = offset.unwrap_or(0);
+            let image = r.start + shift..r.end + shift;
+            for (next_r, next_offset) in next.segments() {
+                let overlap = image.start.max(next_r.start)..image.end.min(next_r.end);
+                if overlap.is_empty() || (offset.is_none() && next_offset.is_none()) {
+                    continue;
+                }
+                pieces.push((
+                    overlap.start - shift..overlap.end - shift,
+                    shift + next_offset.unwrap_or(0),
+                ));
+            }
+        }
+        pieces.sort_by_key(|(r, _)| r.start);
+        RangeMap { pieces }
+    }
+
+    /// The pieces with their offsets and the identity gaps between them, without an offset,
+    /// covering all of `i64`.
+    fn segments(&self) -> impl Iterator<Item = (Range<i64>, Option<i64>)> + '_ {
+        let mut pos = i64::MIN;
+        self.pieces
+            .iter()
+            .map(Some)
+            .chain([None])
+            .flat_map(move |piece| {
+                let end = piece.map_or(i64::MAX, |(r, _)| r.start);
+                let gap = (pos < end).then_some((pos..end, None));
+                if let Some((r, _)) = piece {
+                    pos = r.end;
+                }
+                gap.into_iter()
+                    .chain(piece.map(|(r, offset)| (r.clone(), Some(*offset))))
+            })
+    }
+}
+
+impl FromIterator<(Range<i64>, i64)> for RangeMap {
+    fn from_iter<I: IntoIterator<Item = (Range<i64>, i64)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (range, offset) in iter {
+            map.insert(range, offset);
+        }
+        map
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{IntervalSet, RangeMap};
+
+    #[test]
+    fn insert_merges() {
+        let mut set = IntervalSet::new();
+        set.insert(5..10);
+        set.insert(20..30);
+        set.insert(0..2);
+        assert_eq!(set.ranges(), &[0..2, 5..10, 20..30]);
+        set.insert(10..12);
+        assert_eq!(set.ranges(), &[0..2, 5..12, 20..30]);
+        set.insert(1..25);
+        assert_eq!(set.ranges(), &[0..30]);
+        set.insert(40..40);
+        assert_eq!(set.len(), 30);
+        assert_eq!((set.min(), set.max()), (Some(0), Some(29)));
+        assert!(set.contains(29) && !set.contains(30));
+        assert_eq!(set.to_string(), "{0..30}");
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: IntervalSet = [0..10, 20..30].into_iter().collect();
+        let b: IntervalSet = [5..25].into_iter().collect();
+        assert_eq!(a.union(&b).ranges(), &[0..30]);
+        assert_eq!(a.intersection(&b).ranges(), &[5..10, 20..25]);
+        assert_eq!(a.difference(&b).ranges(), &[0..5, 25..30]);
+        assert_eq!(b.difference(&a).ranges(), &[10..20]);
+        assert!(a.intersection(&IntervalSet::from(10..20)).is_empty());
+
+        let (below, above) = a.split_at(25);
+        assert_eq!(below.ranges(), &[0..10, 20..25]);
+        assert_eq!(above.ranges(), &[25..30]);
+        let (below, above) = a.split_at(10);
+        assert_eq!(below, IntervalSet::from(0..10));
+        assert_eq!(above, IntervalSet::from(20..30));
+    }
+
+    #[test]
+    fn range_map() {
+        // seed-to-soil map of the day 05 example
+        let mut map = RangeMap::new();
+        map.insert_mapping(50, 98, 2);
+        map.insert_mapping(52, 50, 48);
+        assert_eq!(map.apply(79), 81);
+        assert_eq!(map.apply(14), 14);
+        assert_eq!(map.apply(99), 51);
+        assert_eq!(map.apply(100), 100);
+
+        let seeds: IntervalSet = [79..93, 55..68].into_iter().collect();
+        assert_eq!(map.apply_set(&seeds).ranges(), &[57..70, 81..95]);
+        let all = map.apply_set(&IntervalSet::from(0..200));
+        assert_eq!(all, IntervalSet::from(0..200));
+    }
+
+    #[test]
+    fn earlier_pieces_win() {
+        let map: RangeMap = [(0..10, 100), (5..15, 0), (12..20, -10)]
+            .into_iter()
+            .collect();
+        assert_eq!(map.apply(7), 107);
+        assert_eq!(map.apply(11), 11);
+        assert_eq!(map.apply(13), 13);
+        assert_eq!(map.apply(16), 6);
+    }
+
+    #[test]
+    fn composition() {
+        let a: RangeMap = [(0..10, 5), (20..30, -20)].into_iter().collect();
+        let b: RangeMap = [(5..8, 100), (0..5, 20)].into_iter().collect();
+        let ab = a.then(&b);
+        for x in -5..40 {
+            assert_eq!(ab.apply(x), b.apply(a.apply(x)), "{x}");
+        }
+        let set = IntervalSet::from(-5..40);
+        assert_eq!(ab.apply_set(&set), b.apply_set(&a.apply_set(&set)));
+    }
+
+    #[test]
+    fn composition_keeps_cancelled_pieces() {
+        let a: RangeMap = [(0..10, 5)].into_iter().collect();
+        let b: RangeMap = [(5..15, -5)].into_iter().collect();
+        let mut ab = a.then(&b);
+        assert_eq!(ab.apply(3), 3);
+
+        // 0..10 now shifts by zero and 10..15 by -5, only 15..20 is still free
+        ab.insert(0..20, 1000);
+        assert_eq!(ab.apply(3), 3);
+        assert_eq!(ab.apply(12), 12 - 5);
+        assert_eq!(ab.apply(17), 1017);
+    }
+}
@@ -1 +1,11 @@
+pub mod cycle;
+pub mod direction;
+pub mod grid;
+pub mod interval;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub mod polygon;
+pub mod search;
 pub mod template;
+pub mod visualize;
@@ -1,8 +1,16 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, download, read, scaffold,
+    solve::{self, SolveOptions},
+};
 use args::{parse, AppArguments};
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{
+        commands::all,
+        parse_param,
+        readme_benchmarks::{parse_columns, TableOptions},
+        Day,
+    };
     use std::process;
 
     pub enum AppArguments {
@@ -21,10 +29,19 @@ mod args {
             release: bool,
             time: bool,
             submit: Option<u8>,
+            example: bool,
+            visualize: bool,
+            export: Option<String>,
+            export_formats: Option<String>,
+            params: Vec<(String, String)>,
+            verbosity: u8,
         },
         All {
             release: bool,
             time: bool,
+            verbosity: u8,
+            days: Option<Vec<Day>>,
+            table: TableOptions,
         },
     }
 
@@ -35,6 +52,15 @@ mod args {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
                 time: args.contains("--time"),
+                verbosity: verbosity(&mut args),
+                days: args.opt_value_from_fn("--days", all::parse_days)?,
+                table: TableOptions {
+                    columns: args
+                        .opt_value_from_fn("--columns", parse_columns)?
+                        .unwrap_or_default(),
+                    part_totals: args.contains("--part-totals"),
+                    sort: args.opt_value_from_str("--sort")?.unwrap_or_default(),
+                },
             },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
@@ -51,6 +77,12 @@ mod args {
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 time: args.contains("--time"),
+                example: args.contains("--example"),
+                visualize: args.contains("--visualize"),
+                export: args.opt_value_from_str("--export")?,
+                export_formats: args.opt_value_from_str("--export-formats")?,
+                params: args.values_from_fn("--param", parse_param)?,
+                verbosity: verbosity(&mut args),
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -69,6 +101,15 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// 1 for `-v`, 2 for `-vv`.
+    fn verbosity(args: &mut pico_args::Arguments) -> u8 {
+        if args.contains("-vv") {
+            2
+        } else {
+            u8::from(args.contains(["-v", "--verbose"]))
+        }
+    }
 }
 
 fn main() {
@@ -78,7 +119,13 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
+            AppArguments::All {
+                release,
+                time,
+                verbosity,
+                days,
+                table,
+            } => all::handle(release, time, verbosity, days.as_deref(), &table),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold { day, download } => {
@@ -92,7 +139,26 @@ fn main() {
                 release,
                 time,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                example,
+                visualize,
+                export,
+                export_formats,
+                params,
+                verbosity,
+            } => solve::handle(
+                day,
+                release,
+                time,
+                submit,
+                SolveOptions {
+                    example,
+                    visualize,
+                    export,
+                    export_formats,
+                    params,
+                    verbosity,
+                },
+            ),
         },
     };
 }
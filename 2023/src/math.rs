@@ -0,0 +1,384 @@
+/// Number theory helpers shared between days.
+///
+/// Everything that can overflow is checked and returns `None` instead of wrapping, so a
+/// release build fails loudly rather than printing a wrong answer.
+use std::fmt::Debug;
+
+/// The primitive integer types the generic helpers work with.
+pub trait Integer: Copy + Ord + Debug {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    fn checked_rem(self, rhs: Self) -> Option<Self>;
+    fn checked_abs(self) -> Option<Self>;
+}
+
+/// An integer or a reference to one, so the slice helpers accept both `&[T]` and iterators
+/// over `T`.
+pub trait IntegerRef {
+    type Int: Integer;
+
+    fn value(self) -> Self::Int;
+}
+
+macro_rules! impl_integer {
+    ($abs:ident => $($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn checked_div(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_div(self, rhs)
+                }
+
+                fn checked_rem(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_rem(self, rhs)
+                }
+
+                fn checked_abs(self) -> Option<Self> {
+                    impl_integer!(@$abs self)
+                }
+            }
+
+            impl IntegerRef for $t {
+                type Int = $t;
+
+                fn value(self) -> $t {
+                    self
+                }
+            }
+
+            impl IntegerRef for &$t {
+                type Int = $t;
+
+                fn value(self) -> $t {
+                    *self
+                }
+            }
+        )*
+    };
+    (@signed $v:ident) => { $v.checked_abs() };
+    (@unsigned $v:ident) => { Some($v) };
+}
+
+impl_integer!(signed => i8, i16, i32, i64, i128, isize);
+impl_integer!(unsigned => u8, u16, u32, u64, u128, usize);
+
+/// Greatest common divisor, always non-negative. `gcd(0, 0)` is `0`.
+///
+/// `None` if the result does not fit, which only happens for `gcd(MIN, 0)` of a signed type.
+pub fn gcd<T: Integer>(a: T, b: T) -> Option<T> {
+    let (mut a, mut b) = (a, b);
+    while b != T::ZERO {
+        // `MIN % -1` overflows, but the remainder of any division by ±1 is zero.
+        let r = a.checked_rem(b).unwrap_or(T::ZERO);
+        a = b;
+        b = r;
+    }
+    a.checked_abs()
+}
+
+/// Least common multiple, `None` if it overflows. `lcm(0, x)` is `0`.
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    a.checked_div(gcd(a, b)?)?.checked_mul(b)?.checked_abs()
+}
+
+/// Greatest common divisor of all numbers, `0` for an empty input and `None` on overflow.
+pub fn gcd_all<I>(nums: I) -> Option<<I::Item as IntegerRef>::Int>
+where
+    I: IntoIterator,
+    I::Item: IntegerRef,
+{
+    nums.into_iter()
+        .try_fold(Integer::ZERO, |acc, n| gcd(acc, n.value()))
+}
+
+/// Least common multiple of all numbers, `1` for an empty input and `None` on overflow.
+///
+/// ```
+/// use advent_of_code::math::lcm_all;
+///
+/// assert_eq!(lcm_all(&[4u64, 6, 10]), Some(60));
+/// assert_eq!(lcm_all([3, 5].iter().map(|n| n * 2)), Some(30));
+/// ```
+pub fn lcm_all<I>(nums: I) -> Option<<I::Item as IntegerRef>::Int>
+where
+    I: IntoIterator,
+    I::Item: IntegerRef,
+{
+    nums.into_iter()
+        .try_fold(Integer::ONE, |acc, n| lcm(acc, n.value()))
+}
+
+/// Extended Euclid: returns `(g, x, y)` with `a * x + b * y == g == gcd(a, b)`, or `None`
+/// if one of them does not fit in an `i64`, e.g. `g` for `extended_gcd(i64::MIN, 0)`.
+pub fn extended_gcd(a: i64, b: i64) -> Option<(i64, i64, i64)> {
+    // the coefficients are bounded by `|a|` and `|b|`, only the intermediate products need
+    // the wider type.
+    let (mut old_r, mut r) = (a as i128, b as i128);
+    let (mut old_x, mut x) = (1i128, 0i128);
+    let (mut old_y, mut y) = (0i128, 1i128);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (old_r, old_x, old_y) = (-old_r, -old_x, -old_y);
+    }
+
+    Some((
+        i64::try_from(old_r).ok()?,
+        i64::try_from(old_x).ok()?,
+        i64::try_from(old_y).ok()?,
+    ))
+}
+
+/// Solves a system of congruences `x ≡ r (mod m)`, the moduli do not need to be coprime.
+///
+/// Returns the smallest non-negative solution together with the combined modulus (the lcm
+/// of all moduli), or `None` if the system has no solution, a modulus is not positive or
+/// the result overflows.
+///
+/// ```
+/// use advent_of_code::math::crt;
+///
+/// assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// assert_eq!(crt([(1, 4), (3, 6)]), Some((9, 12)));
+/// assert_eq!(crt([(1, 4), (2, 6)]), None);
+/// ```
+pub fn crt<I>(congruences: I) -> Option<(i64, i64)>
+where
+    I: IntoIterator<Item = (i64, i64)>,
+{
+    congruences
+        .into_iter()
+        .try_fold((0i64, 1i64), |(r1, m1), (r2, m2)| {
+            if m2 <= 0 {
+                return None;
+            }
+            let (g, p, _) = extended_gcd(m1, m2)?;
+            let diff = r2 as i128 - r1 as i128;
+            if diff % g as i128 != 0 {
+                return None;
+            }
+            let step = m2 as i128 / g as i128;
+            let m = i64::try_from(m1 as i128 * step).ok()?;
+            let k = (diff / g as i128 * p as i128).rem_euclid(step);
+            let x = (r1 as i128 + m1 as i128 * k).rem_euclid(m as i128);
+            Some((x as i64, m))
+        })
+}
+
+/// `base^exp mod m` by repeated squaring, `None` if `m` is not positive.
+pub fn mod_pow(base: i64, exp: u64, m: i64) -> Option<i64> {
+    if m <= 0 {
+        return None;
+    }
+    let m = m as i128;
+    let mut base = (base as i128).rem_euclid(m);
+    let mut exp = exp;
+    let mut res = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            res = res * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    Some(res as i64)
+}
+
+/// The inverse of `a` modulo `m`, `None` if `a` and `m` are not coprime.
+pub fn mod_inv(a: i64, m: i64) -> Option<i64> {
+    if m <= 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a, m)?;
+    (g == 1).then(|| (x as i128).rem_euclid(m as i128) as i64)
+}
+
+/// The exact integer square root, i.e. the largest `r` with `r * r <= n`.
+pub fn isqrt(n: u64) -> u64 {
+    // the float estimate is off by at most one for large inputs, correct it with integers.
+    let mut r = (n as f64).sqrt() as u64;
+    while r.checked_mul(r).is_none_or(|sq| sq > n) {
+        r -= 1;
+    }
+    while (r + 1).checked_mul(r + 1).is_some_and(|sq| sq <= n) {
+        r += 1;
+    }
+    r
+}
+
+/// Binomial coefficient `n choose k`, `None` if it overflows.
+pub fn binomial(n: u64, k: u64) -> Option<u64> {
+    if k > n {
+        return Some(0);
+    }
+    // coefficients are symmetric
+    let k = k.min(n - k);
+    (0..k).try_fold(1u64, |acc, i| {
+        // `acc * (n - i)` is always divisible by `i + 1`, the product of `i + 1` consecutive
+        // numbers is.
+        let next = acc as u128 * (n - i) as u128 / (i + 1) as u128;
+        u64::try_from(next).ok()
+    })
+}
+
+/// Counts the integers `x` with `a * x^2 + b * x + c < 0`.
+///
+/// Only upward-opening parabolas have a finite count, so `a` has to be positive. Returns
+/// `None` otherwise or if the computation overflows.
+///
+/// ```
+/// use advent_of_code::math::count_below_zero;
+///
+/// // holding the button for `x` of 7ms beats a distance of 9mm: `x * (7 - x) > 9`
+/// assert_eq!(count_below_zero(1, -7, 9), Some(4));
+/// ```
+pub fn count_below_zero(a: i64, b: i64, c: i64) -> Option<u64> {
+    if a <= 0 {
+        return None;
+    }
+    let (a, b, c) = (a as i128, b as i128, c as i128);
+    let d = b
+        .checked_mul(b)?
+        .checked_sub(4i128.checked_mul(a)?.checked_mul(c)?)?;
+    if d <= 0 {
+        return Some(0);
+    }
+    let s = isqrt(u64::try_from(d).ok()?) as i128;
+    let f = |x: i128| -> Option<i128> {
+        a.checked_mul(x)?
+            .checked_add(b)?
+            .checked_mul(x)?
+            .checked_add(c)
+    };
+
+    // `s <= sqrt(d) < s + 1`, so both bounds start just outside of the roots and move in.
+    let mut lo = (-b - s - 1).div_euclid(2 * a);
+    let mut hi = (-b + s + 1).div_euclid(2 * a) + 1;
+    while lo <= hi && f(lo)? >= 0 {
+        lo += 1;
+    }
+    while hi >= lo && f(hi)? >= 0 {
+        hi -= 1;
+    }
+    if lo > hi {
+        return Some(0);
+    }
+    u64::try_from(hi - lo + 1).ok()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), Some(6));
+        assert_eq!(gcd(-12, 18), Some(6));
+        assert_eq!(gcd(0u32, 0), Some(0));
+        assert_eq!(gcd(i64::MIN, -1), Some(1));
+        assert_eq!(gcd(i64::MIN, 0), None);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(u8::MAX, 2), None);
+        assert_eq!(gcd_all(&[12u64, 18, 30]), Some(6));
+        assert_eq!(gcd_all(Vec::<u64>::new()), Some(0));
+        assert_eq!(gcd_all([i8::MIN, 0]), None);
+        assert_eq!(lcm_all(vec![2usize, 3, 4]), Some(12));
+        assert_eq!(lcm_all(&[u64::MAX, 2]), None);
+    }
+
+    #[test]
+    fn extended_euclid() {
+        for (a, b) in [(240, 46), (-7, 3), (0, 5), (i64::MAX, i64::MAX - 1)] {
+            let (g, x, y) = extended_gcd(a, b).unwrap();
+            assert_eq!(Some(g), gcd(a, b));
+            assert_eq!(a as i128 * x as i128 + b as i128 * y as i128, g as i128);
+        }
+        // the gcd is 2^63, one more than `i64::MAX`
+        assert_eq!(extended_gcd(i64::MIN, 0), None);
+        assert_eq!(extended_gcd(i64::MIN, i64::MIN), None);
+    }
+
+    #[test]
+    fn chinese_remainder() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(0, 6), (0, 4)]), Some((0, 12)));
+        assert_eq!(crt([(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([(1, 0)]), None);
+        assert_eq!(crt([(0, i64::MAX), (0, i64::MAX - 1)]), None);
+    }
+
+    #[test]
+    fn modular() {
+        assert_eq!(mod_pow(4, 13, 497), Some(445));
+        assert_eq!(mod_pow(-2, 3, 5), Some(2));
+        assert_eq!(mod_pow(3, 0, 1), Some(0));
+        assert_eq!(mod_pow(i64::MAX, u64::MAX, i64::MAX - 1), Some(1));
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(-3, 11), Some(7));
+        assert_eq!(mod_inv(2, 4), None);
+    }
+
+    #[test]
+    fn square_root() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(isqrt((1 << 52) + 1), 1 << 26);
+    }
+
+    #[test]
+    fn binomials() {
+        assert_eq!(binomial(10, 5), Some(252));
+        assert_eq!(binomial(7, 3), Some(35));
+        assert_eq!(binomial(7, 4), Some(35));
+        assert_eq!(binomial(0, 0), Some(1));
+        assert_eq!(binomial(3, 4), Some(0));
+        assert_eq!(binomial(67, 33), Some(14226520737620288370));
+        assert_eq!(binomial(68, 34), None);
+    }
+
+    #[test]
+    fn quadratic_roots() {
+        assert_eq!(count_below_zero(1, -7, 9), Some(4));
+        assert_eq!(count_below_zero(1, -15, 40), Some(8));
+        // the roots 10 and 20 are exact and do not count
+        assert_eq!(count_below_zero(1, -30, 200), Some(9));
+        assert_eq!(count_below_zero(1, -71530, 940200), Some(71503));
+        assert_eq!(count_below_zero(1, 0, 0), Some(0));
+        assert_eq!(count_below_zero(2, 0, -8), Some(3));
+        assert_eq!(count_below_zero(-1, 0, 1), None);
+    }
+}
@@ -0,0 +1,150 @@
+/// Memoization for recursive solutions.
+///
+/// A [`Memo`] is handed down the recursion instead of a bare `HashMap`. Keys may borrow from
+/// the input, e.g. `(&'a [char], &'a [u32])`, as long as the memo does not outlive it.
+use std::{collections::HashMap, fmt::Display, hash::Hash};
+
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    hits: u64,
+    misses: u64,
+}
+
+/// How often a [`Memo`] could answer from its cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo {
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Returns the cached value for `key`, or computes and caches it.
+    ///
+    /// `compute` gets the memo back, so it can recurse through it.
+    ///
+    /// ```
+    /// use advent_of_code::memo::Memo;
+    ///
+    /// fn fib(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+    ///     memo.get_or_compute(n, |memo| {
+    ///         if n < 2 { n } else { fib(n - 1, memo) + fib(n - 2, memo) }
+    ///     })
+    /// }
+    ///
+    /// let mut memo = Memo::new();
+    /// assert_eq!(fib(90, &mut memo), 2880067194370816120);
+    /// assert_eq!(memo.stats().misses, 91);
+    /// ```
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+        self.misses += 1;
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        MemoStats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.cache.len(),
+        }
+    }
+
+    /// Forgets all values, but keeps the counters.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoStats {
+    /// The share of lookups answered from the cache, between `0.0` and `1.0`.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+impl Display for MemoStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate), {} entries",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0,
+            self.entries
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Memo;
+
+    // number of ways to climb `n` stairs taking 1 or 2 at once
+    fn stairs<'a>(steps: &'a [u8], memo: &mut Memo<&'a [u8], u64>) -> u64 {
+        memo.get_or_compute(steps, |memo| match steps {
+            [] | [_] => 1,
+            [_, rest @ ..] => stairs(rest, memo) + stairs(&rest[1..], memo),
+        })
+    }
+
+    #[test]
+    fn borrowed_keys() {
+        let steps = vec![0u8; 50];
+        let mut memo = Memo::new();
+        assert_eq!(stairs(&steps, &mut memo), 20365011074);
+
+        let stats = memo.stats();
+        assert_eq!(stats.misses, 51);
+        assert_eq!(stats.entries, 51);
+        assert_eq!(stats.hits, 48);
+        assert_eq!(memo.get(&&steps[48..]), Some(&2));
+    }
+
+    #[test]
+    fn stats() {
+        let mut memo: Memo<u32, u32> = Memo::new();
+        assert_eq!(memo.stats().hit_rate(), 0.0);
+        memo.get_or_compute(1, |_| 10);
+        memo.get_or_compute(1, |_| unreachable!());
+        memo.get_or_compute(1, |_| unreachable!());
+        memo.get_or_compute(2, |_| 20);
+        assert_eq!(
+            memo.stats().to_string(),
+            "2 hits, 2 misses (50.0% hit rate), 2 entries"
+        );
+        memo.clear();
+        assert_eq!(memo.stats().entries, 0);
+    }
+}
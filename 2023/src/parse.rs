@@ -0,0 +1,307 @@
+/// Ready-made nom combinators for the shapes puzzle inputs keep coming in.
+///
+/// All parsers use the default [`nom::error::Error`], so they compose with the hand-written
+/// parsers of the solutions.
+use std::{fmt::Display, str::FromStr};
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{
+        char, digit1, line_ending, multispace0, none_of, one_of, space0, space1,
+    },
+    combinator::{all_consuming, map_opt, map_res, opt, recognize},
+    error::{Error, ErrorKind},
+    multi::{many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated},
+    Finish, IResult,
+};
+
+use crate::grid::Grid;
+
+/// An unsigned decimal number, e.g. `42`.
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse)(input)
+}
+
+/// A decimal number with an optional sign, e.g. `-42` or `+7`.
+pub fn signed<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(preceded(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// Unsigned numbers separated by spaces, e.g. `41 48  83 86 17`. Leading spaces are skipped.
+pub fn numbers<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+    preceded(space0, separated_list1(space1, unsigned))(input)
+}
+
+/// Signed numbers separated by spaces, e.g. `0 -3 6 9`. Leading spaces are skipped.
+pub fn signed_numbers<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+    preceded(space0, separated_list1(space1, signed))(input)
+}
+
+/// Items separated by commas and optional spaces, e.g. `a, b,c`.
+pub fn comma_list<'a, O, F>(item: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    separated_list1(pair(char(','), space0), item)
+}
+
+/// A key and a value split by `sep` with optional spaces around it, e.g. `AAA = (BBB, CCC)`.
+pub fn key_value<'a, K, V, F, G>(
+    key: F,
+    sep: &'static str,
+    value: G,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (K, V)>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, K>,
+    G: FnMut(&'a str) -> IResult<&'a str, V>,
+{
+    separated_pair(key, delimited(space0, tag(sep), space0), value)
+}
+
+/// A key followed by a comma separated list, e.g. `broadcaster -> a, b, c`.
+pub fn key_list<'a, K, V, F, G>(
+    key: F,
+    sep: &'static str,
+    value: G,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (K, Vec<V>)>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, K>,
+    G: FnMut(&'a str) -> IResult<&'a str, V>,
+{
+    key_value(key, sep, comma_list(value))
+}
+
+/// One item per line.
+pub fn lines<'a, O, F>(item: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    separated_list1(line_ending, item)
+}
+
+/// Blocks separated by a blank line, e.g. the patterns of day 13.
+pub fn blocks<'a, O, F>(block: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    separated_list1(pair(line_ending, line_ending), block)
+}
+
+/// A rectangular map of characters, each converted by `cell`.
+///
+/// The grid ends at a blank line or at the first character `cell` returns `None` for. Rows of
+/// different lengths are an error.
+pub fn grid<'a, T, F>(mut cell: F) -> impl FnMut(&'a str) -> IResult<&'a str, Grid<T>>
+where
+    F: FnMut(char) -> Option<T>,
+{
+    move |input: &'a str| {
+        let (rest, rows) =
+            separated_list1(line_ending, many1(map_opt(none_of("\r\n"), &mut cell)))(input)?;
+        let width = rows[0].len();
+        if let Some(ragged) = rows.iter().position(|row| row.len() != width) {
+            // point the error to the start of the offending row
+            let offset = input.split_inclusive('\n').take(ragged).map(str::len).sum();
+            return Err(nom::Err::Error(Error::new(
+                &input[offset..],
+                ErrorKind::Verify,
+            )));
+        }
+        Ok((
+            rest,
+            Grid::from_vec(width, rows.into_iter().flatten().collect()),
+        ))
+    }
+}
+
+/// All integers in `line`, ignoring whatever is around them. A `-` in front of a digit is a
+/// sign unless it directly follows another digit, as in the range `4-5`. Numbers that do not
+/// fit in an `i64` are skipped.
+///
+/// ```
+/// use advent_of_code::parse::integers;
+///
+/// assert_eq!(integers("Card 3: x=-12, y=7..9"), vec![3, -12, 7, 9]);
+/// assert_eq!(integers("pages 4-5"), vec![4, 5]);
+/// ```
+pub fn integers(line: &str) -> Vec<i64> {
+    let mut res = vec![];
+    let mut pos = 0;
+    while let Some(offset) = line[pos..].find(|c: char| c.is_ascii_digit()) {
+        let digits = pos + offset;
+        let end = line[digits..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(line.len(), |len| digits + len);
+        let before = line[..digits].strip_suffix('-');
+        let negative = before.is_some_and(|b| !b.ends_with(|c: char| c.is_ascii_digit()));
+        // parse the sign along with the digits, so that `i64::MIN` fits as well
+        let start = if negative { digits - 1 } else { digits };
+        if let Ok(value) = line[start..end].parse() {
+            res.push(value);
+        }
+        pos = end;
+    }
+    res
+}
+
+/// Runs `parser` on the whole input, only allowing trailing whitespace to be left over.
+pub fn parse_to_end<'a, O, F>(parser: F, input: &'a str) -> Result<O, Error<&'a str>>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    all_consuming(terminated(parser, multispace0))(input)
+        .finish()
+        .map(|(_, output)| output)
+}
+
+/// Like [`parse_to_end`], but points to the offending spot of the input on failure.
+///
+/// ```
+/// use advent_of_code::parse::{lines, numbers, parse_all};
+///
+/// let err = parse_all(lines(numbers::<u32>), "1 2\n3 x").unwrap_err();
+/// assert_eq!((err.line, err.column), (2, 3));
+/// ```
+pub fn parse_all<'a, O, F>(parser: F, input: &'a str) -> Result<O, ParseError>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    parse_to_end(parser, input).map_err(|err| ParseError::new(input, err))
+}
+
+/// A parser failure, located by line and column (both starting at 1).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// The line the error occurred in, without its line break.
+    pub source_line: String,
+    pub kind: ErrorKind,
+}
+
+impl ParseError {
+    /// Locates a nom error, whose input is a suffix of `input`.
+    pub fn new(input: &str, err: Error<&str>) -> Self {
+        let offset = input.len() - err.input.len();
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            source_line: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            kind: err.code,
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        let reason = match self.kind {
+            // what `all_consuming` reports for input left over after the parser
+            ErrorKind::Eof => "expected end of input",
+            _ => self.kind.description(),
+        };
+        writeln!(
+            f,
+            "parse error at line {}, column {}: {reason}",
+            self.line, self.column
+        )?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(f, "{gutter} | {}^", " ".repeat(self.column - 1))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use nom::character::complete::alpha1;
+
+    use super::*;
+
+    #[test]
+    fn number_lists() {
+        assert_eq!(numbers::<u32>("  41 48  83"), Ok(("", vec![41, 48, 83])));
+        assert_eq!(
+            signed_numbers::<i64>("0 -3 +6\nrest"),
+            Ok(("\nrest", vec![0, -3, 6]))
+        );
+        assert!(unsigned::<u8>("300").is_err());
+        assert!(unsigned::<u32>("-3").is_err());
+    }
+
+    #[test]
+    fn keys_and_lists() {
+        let mut module = key_list(alpha1, "->", alpha1);
+        assert_eq!(
+            module("broadcaster -> a, b,c"),
+            Ok(("", ("broadcaster", vec!["a", "b", "c"])))
+        );
+        let mut node = key_value(alpha1, "=", unsigned::<u32>);
+        assert_eq!(node("AAA=12"), Ok(("", ("AAA", 12))));
+    }
+
+    #[test]
+    fn blocks_of_grids() {
+        let input = "#.\n.#\n\n##\n..\n";
+        let cell = |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+        let grids = parse_to_end(blocks(grid(cell)), input).unwrap();
+        assert_eq!(grids.len(), 2);
+        assert_eq!(grids[1].row(0), &[true, true]);
+        assert!(grid(cell)("#.\n#").is_err());
+    }
+
+    #[test]
+    fn extracts_integers() {
+        assert_eq!(integers("Game 12: 3 blue, 4-5"), vec![12, 3, 4, 5]);
+        assert_eq!(integers("-1--2"), vec![-1, -2]);
+        assert!(integers("no numbers").is_empty());
+        assert_eq!(
+            integers("-9223372036854775808 99999999999999999999 1"),
+            vec![i64::MIN, 1]
+        );
+    }
+
+    #[test]
+    fn parses_to_end() {
+        assert_eq!(
+            parse_to_end(lines(numbers::<u32>), "1 2\n3\n\n"),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+        assert!(parse_to_end(lines(numbers::<u32>), "1 2\nx").is_err());
+    }
+
+    #[test]
+    fn locates_errors() {
+        let input = "1 2\r\n3 4\r\n5 x 6\r\n";
+        let err = parse_all(lines(numbers::<u32>), input).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 3));
+        assert_eq!(err.source_line, "5 x 6");
+        assert_eq!(
+            err.to_string(),
+            "parse error at line 3, column 3: expected end of input\n3 | 5 x 6\n  |   ^"
+        );
+
+        let err = parse_all(numbers::<u32>, "x").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+        assert_eq!(err.kind, ErrorKind::Digit);
+
+        let err = parse_all(grid(Some), "ab\ncd\ne\n").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.kind, ErrorKind::Verify);
+    }
+}
@@ -0,0 +1,260 @@
+/// Simple polygons on the integer lattice.
+///
+/// A [`Polygon`] is a closed path of vertices, the last vertex connects back to the first.
+/// Coordinates follow the [`crate::direction`] convention and can be any signed integer type,
+/// `i64` or `i128` if the path is long enough for the doubled area to overflow.
+use std::{
+    iter::Sum,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use crate::{
+    direction::{Direction, Pos},
+    math::{gcd, Integer},
+};
+
+/// A signed integer type usable as polygon coordinate.
+pub trait Coord:
+    Integer
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + Sum
+    + From<i32>
+{
+}
+
+impl<T> Coord for T where
+    T: Integer
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + Sum
+        + From<i32>
+{
+}
+
+/// Where a point lies relative to a [`Polygon`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon<T> {
+    vertices: Vec<(T, T)>,
+}
+
+impl<T: Coord> Polygon<T> {
+    pub fn new(vertices: Vec<(T, T)>) -> Self {
+        Polygon { vertices }
+    }
+
+    /// Builds the polygon traced by following `moves` from the origin.
+    ///
+    /// The path is expected to end where it started, like the dig plan of day 18.
+    pub fn from_moves(moves: impl IntoIterator<Item = (Direction, T)>) -> Self {
+        let mut pos = (T::ZERO, T::ZERO);
+        let mut vertices = vec![];
+        for (dir, steps) in moves {
+            vertices.push(pos);
+            let offset = dir.offset();
+            pos = (
+                pos.0 + T::from(offset.x) * steps,
+                pos.1 + T::from(offset.y) * steps,
+            );
+        }
+        Polygon { vertices }
+    }
+
+    /// Builds the polygon from the positions visited by a closed path, like a pipe loop.
+    pub fn from_positions(positions: impl IntoIterator<Item = Pos>) -> Self {
+        let vertices = positions
+            .into_iter()
+            .map(|p| (T::from(p.x), T::from(p.y)))
+            .collect();
+        Polygon { vertices }
+    }
+
+    pub fn vertices(&self) -> &[(T, T)] {
+        &self.vertices
+    }
+
+    /// All edges as pairs of vertices, including the closing one.
+    pub fn edges(&self) -> impl Iterator<Item = ((T, T), (T, T))> + '_ {
+        self.vertices
+            .iter()
+            .copied()
+            .zip(self.vertices.iter().copied().cycle().skip(1))
+    }
+
+    /// Twice the signed area by the shoelace formula, positive if the vertices run
+    /// counter-clockwise in the usual maths orientation, i.e. clockwise on screen.
+    ///
+    /// Doubling keeps the result exact, the area of a lattice polygon can end in `.5`.
+    pub fn twice_signed_area(&self) -> T {
+        self.edges()
+            .map(|((x1, y1), (x2, y2))| x1 * y2 - x2 * y1)
+            .sum()
+    }
+
+    /// The enclosed area, rounded down if it is not a whole number.
+    pub fn area(&self) -> T {
+        self.twice_signed_area()
+            .checked_abs()
+            .expect("area overflows")
+            / T::from(2)
+    }
+
+    /// The number of lattice points on the boundary, i.e. the length of a path of unit moves.
+    pub fn boundary(&self) -> T {
+        self.edges()
+            .map(|((x1, y1), (x2, y2))| gcd(x2 - x1, y2 - y1).expect("boundary overflows"))
+            .sum()
+    }
+
+    /// The number of lattice points strictly inside, by Pick's theorem `A = I + B / 2 - 1`.
+    pub fn interior(&self) -> T {
+        let twice_area = self
+            .twice_signed_area()
+            .checked_abs()
+            .expect("area overflows");
+        (twice_area - self.boundary() + T::from(2)) / T::from(2)
+    }
+
+    /// The number of lattice points inside or on the boundary, e.g. the tiles of a dug out
+    /// lagoon including its trench.
+    pub fn lattice_points(&self) -> T {
+        self.interior() + self.boundary()
+    }
+
+    pub fn locate(&self, point: (T, T)) -> Location {
+        let (px, py) = point;
+        let mut inside = false;
+        for ((x1, y1), (x2, y2)) in self.edges() {
+            let cross = (x2 - x1) * (py - y1) - (y2 - y1) * (px - x1);
+            if cross == T::ZERO
+                && x1.min(x2) <= px
+                && px <= x1.max(x2)
+                && y1.min(y2) <= py
+                && py <= y1.max(y2)
+            {
+                return Location::Boundary;
+            }
+            // cast a ray to the right, counting edges that cross the half-open row of `py`
+            if (y1 > py) != (y2 > py) {
+                // `px` lies left of the crossing if the cross product has the edge's sign
+                let left = if y2 > y1 {
+                    cross > T::ZERO
+                } else {
+                    cross < T::ZERO
+                };
+                if left {
+                    inside = !inside;
+                }
+            }
+        }
+        if inside {
+            Location::Inside
+        } else {
+            Location::Outside
+        }
+    }
+
+    /// Whether `point` lies inside the polygon or on its boundary.
+    pub fn contains(&self, point: (T, T)) -> bool {
+        self.locate(point) != Location::Outside
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::direction::{Direction, Pos};
+
+    use super::{Location, Polygon};
+
+    #[test]
+    fn square() {
+        let square = Polygon::<i64>::new(vec![(0, 0), (4, 0), (4, 4), (0, 4)]);
+        assert_eq!(square.twice_signed_area(), 32);
+        assert_eq!(square.area(), 16);
+        assert_eq!(square.boundary(), 16);
+        assert_eq!(square.interior(), 9);
+        assert_eq!(square.lattice_points(), 25);
+
+        let reversed = Polygon::<i64>::new(square.vertices().iter().rev().copied().collect());
+        assert_eq!(reversed.twice_signed_area(), -32);
+        assert_eq!(reversed.interior(), 9);
+    }
+
+    #[test]
+    fn triangle() {
+        // area 4.5, 3 + 3 + 3 boundary points
+        let triangle = Polygon::<i128>::new(vec![(0, 0), (3, 0), (0, 3)]);
+        assert_eq!(triangle.twice_signed_area(), 9);
+        assert_eq!(triangle.area(), 4);
+        assert_eq!(triangle.boundary(), 9);
+        assert_eq!(triangle.interior(), 1);
+    }
+
+    #[test]
+    fn moves_and_positions() {
+        let moves = [
+            (Direction::Right, 2),
+            (Direction::Down, 2),
+            (Direction::Left, 2),
+            (Direction::Up, 2),
+        ];
+        let polygon = Polygon::<i64>::from_moves(moves);
+        assert_eq!(polygon.vertices(), &[(0, 0), (2, 0), (2, 2), (0, 2)]);
+        assert_eq!(polygon.lattice_points(), 9);
+
+        let ring = [
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (2, 1),
+            (2, 2),
+            (1, 2),
+            (0, 2),
+            (0, 1),
+        ];
+        let polygon = Polygon::<i64>::from_positions(ring.map(|(x, y)| Pos::new(x, y)));
+        assert_eq!(polygon.boundary(), 8);
+        assert_eq!(polygon.interior(), 1);
+    }
+
+    #[test]
+    fn point_in_polygon() {
+        // a U shape, open at the top
+        let u = Polygon::<i64>::new(vec![
+            (0, 0),
+            (2, 0),
+            (2, 4),
+            (4, 4),
+            (4, 0),
+            (6, 0),
+            (6, 6),
+            (0, 6),
+        ]);
+        assert_eq!(u.locate((1, 1)), Location::Inside);
+        assert_eq!(u.locate((5, 5)), Location::Inside);
+        assert_eq!(u.locate((3, 2)), Location::Outside);
+        assert_eq!(u.locate((3, 4)), Location::Boundary);
+        assert_eq!(u.locate((6, 3)), Location::Boundary);
+        assert_eq!(u.locate((1, 4)), Location::Inside);
+        assert_eq!(u.locate((-1, 4)), Location::Outside);
+        assert_eq!(u.locate((7, 0)), Location::Outside);
+        assert!(u.contains((0, 0)));
+        assert!(!u.contains((3, 0)));
+        assert_eq!(u.lattice_points(), 49 - 4);
+    }
+}
@@ -0,0 +1,455 @@
+/// Shortest paths through state spaces.
+///
+/// All searches take any number of start nodes, a `successors` function and a `success`
+/// predicate, and report the path they found together with how much work it took. Walkers
+/// that must keep going straight for a while before they may turn, like the crucibles of
+/// day 17, are described by [`MoveLimits`] instead of remembering their last moves.
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    fmt::Display,
+    hash::Hash,
+    ops::Add,
+};
+
+use crate::{
+    direction::{Direction, Pos},
+    math::Integer,
+};
+
+/// An integer type usable as edge cost.
+pub trait Cost: Integer + Add<Output = Self> {}
+
+impl<T> Cost for T where T: Integer + Add<Output = T> {}
+
+/// A path from one of the start nodes to a node satisfying the success predicate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    /// The nodes of the path, including its start and its end.
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+/// How much of the state space a search had to look at.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// Distinct nodes reached, including the starts.
+    pub visited: usize,
+    /// Nodes whose successors were generated.
+    pub expanded: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult<N, C> {
+    /// The cheapest path, or `None` if no node satisfying the predicate can be reached.
+    pub path: Option<Path<N, C>>,
+    pub stats: SearchStats,
+}
+
+impl<N, C: Copy> SearchResult<N, C> {
+    pub fn cost(&self) -> Option<C> {
+        self.path.as_ref().map(|path| path.cost)
+    }
+
+    /// The node the path ends at.
+    pub fn goal(&self) -> Option<&N> {
+        self.path.as_ref().and_then(|path| path.nodes.last())
+    }
+}
+
+impl Display for SearchStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} visited, {} expanded", self.visited, self.expanded)
+    }
+}
+
+/// The nodes reached so far, each with the index of the node it was reached from.
+struct Visited<N> {
+    nodes: Vec<(N, usize)>,
+    index: HashMap<N, usize>,
+}
+
+impl<N: Hash + Eq + Clone> Visited<N> {
+    fn new() -> Self {
+        Visited {
+            nodes: vec![],
+            index: HashMap::new(),
+        }
+    }
+
+    /// Adds `node` unless it was seen before, returning its index either way.
+    fn insert(&mut self, node: N, parent: usize) -> (usize, bool) {
+        match self.index.entry(node) {
+            Entry::Occupied(entry) => (*entry.get(), false),
+            Entry::Vacant(entry) => {
+                let idx = self.nodes.len();
+                self.nodes.push((entry.key().clone(), parent));
+                entry.insert(idx);
+                (idx, true)
+            }
+        }
+    }
+
+    /// Follows the parents from `idx` back to a start, which is its own parent.
+    fn path(&self, mut idx: usize) -> Vec<N> {
+        let mut path = vec![self.nodes[idx].0.clone()];
+        while self.nodes[idx].1 != idx {
+            idx = self.nodes[idx].1;
+            path.push(self.nodes[idx].0.clone());
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Breadth-first search, the cost of a path being its number of moves.
+///
+/// ```
+/// use advent_of_code::search::bfs;
+///
+/// // from 1 to 100 by doubling or adding one
+/// let res = bfs([1u32], |&n| [n * 2, n + 1].into_iter().filter(|&n| n <= 100), |&n| n == 100);
+/// assert_eq!(res.path.unwrap().nodes, vec![1, 2, 3, 6, 12, 24, 25, 50, 100]);
+/// ```
+pub fn bfs<N, FN, IN, FS>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: FN,
+    mut success: FS,
+) -> SearchResult<N, usize>
+where
+    N: Hash + Eq + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> bool,
+{
+    let mut visited = Visited::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        let idx = visited.nodes.len();
+        if visited.insert(start, idx).1 {
+            queue.push_back((idx, 0));
+        }
+    }
+
+    let mut expanded = 0;
+    while let Some((idx, steps)) = queue.pop_front() {
+        let node = visited.nodes[idx].0.clone();
+        if success(&node) {
+            return SearchResult {
+                path: Some(Path {
+                    nodes: visited.path(idx),
+                    cost: steps,
+                }),
+                stats: SearchStats {
+                    visited: visited.nodes.len(),
+                    expanded,
+                },
+            };
+        }
+        expanded += 1;
+        for next in successors(&node) {
+            let (next_idx, new) = visited.insert(next, idx);
+            if new {
+                queue.push_back((next_idx, steps + 1));
+            }
+        }
+    }
+
+    SearchResult {
+        path: None,
+        stats: SearchStats {
+            visited: visited.nodes.len(),
+            expanded,
+        },
+    }
+}
+
+/// The number of moves from the nearest start to every reachable node.
+///
+/// Stops expanding nodes `max_steps` moves away, pass `usize::MAX` to flood everything.
+pub fn distances<N, FN, IN>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: FN,
+    max_steps: usize,
+) -> HashMap<N, usize>
+where
+    N: Hash + Eq + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    let mut dist = HashMap::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if let Entry::Vacant(entry) = dist.entry(start.clone()) {
+            entry.insert(0);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, steps)) = queue.pop_front() {
+        if steps == max_steps {
+            continue;
+        }
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = dist.entry(next.clone()) {
+                entry.insert(steps + 1);
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+    dist
+}
+
+/// Dijkstra's algorithm, for successors yielding non-negative move costs.
+pub fn dijkstra<N, C, FN, IN, FS>(
+    starts: impl IntoIterator<Item = N>,
+    successors: FN,
+    success: FS,
+) -> SearchResult<N, C>
+where
+    N: Hash + Eq + Clone,
+    C: Cost,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    astar(starts, successors, |_| C::ZERO, success)
+}
+
+/// A* search. `heuristic` estimates the remaining cost and must never overestimate it, or a
+/// more expensive path may be returned.
+///
+/// ```
+/// use advent_of_code::search::astar;
+///
+/// // walk from 0 to 10 in steps of 1 costing 2 or steps of 3 costing 5
+/// let res = astar(
+///     [0i32],
+///     |&n| [(n + 1, 2), (n + 3, 5)],
+///     |&n| (10 - n).max(0) * 5 / 3,
+///     |&n| n == 10,
+/// );
+/// assert_eq!(res.cost(), Some(17));
+/// ```
+pub fn astar<N, C, FN, IN, FH, FS>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut success: FS,
+) -> SearchResult<N, C>
+where
+    N: Hash + Eq + Clone,
+    C: Cost,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+{
+    let mut visited = Visited::new();
+    // best known cost of every visited node, by index
+    let mut costs: Vec<C> = vec![];
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        let idx = visited.nodes.len();
+        if visited.insert(start.clone(), idx).1 {
+            costs.push(C::ZERO);
+            heap.push(Reverse((heuristic(&start), C::ZERO, idx)));
+        }
+    }
+
+    let mut expanded = 0;
+    while let Some(Reverse((_, cost, idx))) = heap.pop() {
+        if cost > costs[idx] {
+            // a cheaper way to this node was found after it was queued
+            continue;
+        }
+        let node = visited.nodes[idx].0.clone();
+        if success(&node) {
+            return SearchResult {
+                path: Some(Path {
+                    nodes: visited.path(idx),
+                    cost,
+                }),
+                stats: SearchStats {
+                    visited: visited.nodes.len(),
+                    expanded,
+                },
+            };
+        }
+        expanded += 1;
+        for (next, move_cost) in successors(&node) {
+            let next_cost = cost + move_cost;
+            let (next_idx, new) = visited.insert(next.clone(), idx);
+            if new {
+                costs.push(next_cost);
+            } else if next_cost < costs[next_idx] {
+                costs[next_idx] = next_cost;
+                visited.nodes[next_idx].1 = idx;
+            } else {
+                continue;
+            }
+            heap.push(Reverse((next_cost + heuristic(&next), next_cost, next_idx)));
+        }
+    }
+
+    SearchResult {
+        path: None,
+        stats: SearchStats {
+            visited: visited.nodes.len(),
+            expanded,
+        },
+    }
+}
+
+/// A walker on a map which moves at least `min` and at most `max` steps in a line before it
+/// may turn or stop. It never reverses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveLimits {
+    pub min: u32,
+    pub max: u32,
+}
+
+/// The search state of a walker bound by [`MoveLimits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Run {
+    pub pos: Pos,
+    /// The direction of the last move, `None` before the first one.
+    pub dir: Option<Direction>,
+    /// The number of moves made in `dir` in a row.
+    pub len: u32,
+}
+
+impl Run {
+    /// A walker standing at `pos`, free to leave in any direction.
+    pub fn start(pos: Pos) -> Self {
+        Run {
+            pos,
+            dir: None,
+            len: 0,
+        }
+    }
+}
+
+impl MoveLimits {
+    pub fn new(min: u32, max: u32) -> Self {
+        MoveLimits { min, max }
+    }
+
+    /// The states one step further, ignoring the edges of the map.
+    pub fn successors(&self, run: &Run) -> impl Iterator<Item = Run> {
+        let run = *run;
+        let limits = *self;
+        Direction::ALL.into_iter().filter_map(move |dir| {
+            let len = match run.dir {
+                None => 1,
+                Some(last) if last == dir => run.len + 1,
+                Some(last) if last == dir.opposite() => return None,
+                Some(_) if run.len < limits.min => return None,
+                Some(_) => 1,
+            };
+            (len <= limits.max).then_some(Run {
+                pos: run.pos + dir,
+                dir: Some(dir),
+                len,
+            })
+        })
+    }
+
+    /// Whether the walker may stop here.
+    pub fn can_stop(&self, run: &Run) -> bool {
+        run.dir.is_none() || run.len >= self.min
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{direction::Pos, grid::Grid};
+
+    use super::{astar, bfs, dijkstra, distances, MoveLimits, Run};
+
+    fn maze() -> Grid<char> {
+        let rows = ["S.#.....", ".##.###.", "....#...", ".##...#E"];
+        Grid::from_vec(8, rows.iter().flat_map(|r| r.chars()).collect())
+    }
+
+    fn open(grid: &Grid<char>, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        grid.neighbours_4(pos)
+            .filter(|(_, &c)| c != '#')
+            .map(|(p, _)| p)
+    }
+
+    #[test]
+    fn searches_agree() {
+        let grid = maze();
+        let start = grid.find(&'S').unwrap();
+        let end = grid.find(&'E').unwrap();
+
+        let by_bfs = bfs([start], |&p| open(&grid, p), |&p| p == end);
+        let by_dijkstra = dijkstra([start], |&p| open(&grid, p).map(|p| (p, 1)), |&p| p == end);
+        let by_astar = astar(
+            [start],
+            |&p| open(&grid, p).map(|p| (p, 1)),
+            |&p| (end - p).abs().dot(Pos::ONE),
+            |&p| p == end,
+        );
+        assert_eq!(by_bfs.cost(), Some(12));
+        assert_eq!(by_dijkstra.cost(), Some(12));
+        assert_eq!(by_astar.cost(), Some(12));
+
+        let path = by_astar.path.unwrap().nodes;
+        assert_eq!(path.len(), 13);
+        assert_eq!((path[0], path[12]), (start, end));
+        assert!(path
+            .windows(2)
+            .all(|w| (w[1] - w[0]).abs().dot(Pos::ONE) == 1));
+        assert!(by_astar.stats.expanded <= by_dijkstra.stats.expanded);
+        assert!(by_dijkstra.stats.visited >= by_dijkstra.stats.expanded);
+    }
+
+    #[test]
+    fn multiple_starts_and_dead_ends() {
+        let grid = maze();
+        let end = grid.find(&'E').unwrap();
+        let starts = [Pos::new(0, 0), Pos::new(5, 0)];
+        let res = bfs(starts, |&p| open(&grid, p), |&p| p == end);
+        assert_eq!(res.cost(), Some(5));
+        assert_eq!(res.path.unwrap().nodes[0], Pos::new(5, 0));
+
+        let res = bfs(starts, |&p| open(&grid, p), |&p| p == Pos::new(2, 0));
+        assert_eq!(res.path, None);
+        assert_eq!(res.stats.visited, 22);
+
+        let dist = distances(starts, |&p| open(&grid, p), usize::MAX);
+        assert_eq!(dist.len(), 22);
+        assert_eq!(dist[&end], 5);
+        assert_eq!(distances(starts, |&p| open(&grid, p), 1).len(), 6);
+    }
+
+    #[test]
+    fn move_limits() {
+        let limits = MoveLimits::new(2, 3);
+        let start = Run::start(Pos::ZERO);
+        assert_eq!(limits.successors(&start).count(), 4);
+        assert!(limits.can_stop(&start));
+
+        let right = limits.successors(&start).find(|r| r.pos == Pos::new(1, 0));
+        let right = right.unwrap();
+        assert!(!limits.can_stop(&right));
+        // may not turn yet
+        let next: Vec<_> = limits.successors(&right).collect();
+        assert_eq!(next.len(), 1);
+        assert_eq!((next[0].pos, next[0].len), (Pos::new(2, 0), 2));
+        // left, right or straight on
+        assert_eq!(limits.successors(&next[0]).count(), 3);
+
+        // on an open field, 7 to the right takes a detour of 2 down and 2 back up
+        let res = dijkstra(
+            [start],
+            |run| limits.successors(run).map(|r| (r, 1)),
+            |run| run.pos == Pos::new(7, 0) && limits.can_stop(run),
+        );
+        assert_eq!(res.cost(), Some(11));
+    }
+}
@@ -1,28 +1,42 @@
-use std::io;
+use std::{fs, io};
 
 use crate::template::{
     all_days,
-    readme_benchmarks::{self, Timings},
-    Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    readme_benchmarks::{self, TableOptions, Timings},
+    Day, DayFromStrError, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
 
-pub fn handle(is_release: bool, is_timed: bool) {
+pub fn handle(
+    is_release: bool,
+    is_timed: bool,
+    verbosity: u8,
+    days: Option<&[Day]>,
+    table: &TableOptions,
+) {
     let mut timings: Vec<Timings> = vec![];
 
-    all_days().for_each(|day| {
-        if day > 1 {
+    let days: Vec<Day> = days.map_or_else(|| all_days().collect(), <[Day]>::to_vec);
+
+    days.into_iter().enumerate().for_each(|(i, day)| {
+        if i > 0 {
             println!();
         }
 
         println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
         println!("------");
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let output = child_commands::run_solution(day, is_timed, is_release, verbosity).unwrap();
 
         if output.is_empty() {
             println!("Not solved.");
+            // keeps the readme rows of this day, marked as stale.
+            timings.push(Timings::empty(day));
         } else {
-            let val = child_commands::parse_exec_time(&output, day);
+            if is_timed {
+                warn_stray_lines(day, &output);
+            }
+            let mut val = child_commands::parse_exec_time(&output, day);
+            val.title = read_puzzle_title(day).or_else(|| child_commands::parse_title(&output));
             timings.push(val);
         }
     });
@@ -33,7 +47,7 @@ pub fn handle(is_release: bool, is_timed: bool) {
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
         if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
+            match readme_benchmarks::update(timings, total_millis, table) {
                 Ok(()) => println!("Successfully updated README with benchmarks."),
                 Err(_) => {
                     eprintln!("Failed to update readme with benchmarks.");
@@ -61,11 +75,40 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
+/// Parses a comma-separated list of days, e.g. `5,14`.
+pub fn parse_days(s: &str) -> Result<Vec<Day>, DayFromStrError> {
+    let mut days = s
+        .split(',')
+        .map(|d| d.trim().parse())
+        .collect::<Result<Vec<Day>, _>>()?;
+    days.sort();
+    days.dedup();
+    Ok(days)
+}
+
+/// Warns if the timed run of `day`, whose stdout is `output`, printed lines besides its
+/// results.
+pub fn warn_stray_lines(day: Day, output: &[String]) {
+    let stray = child_commands::stray_lines(output);
+    if !stray.is_empty() {
+        eprintln!(
+            "Warning: day {day} printed {} line(s) besides its results, which can skew its timings. Use aoc_debug! instead.",
+            stray.len()
+        );
+    }
+}
+
+/// Reads the puzzle title from the description downloaded by `cargo download`, if present.
+fn read_puzzle_title(day: Day) -> Option<String> {
+    let description = fs::read_to_string(format!("data/puzzles/{day}.md")).ok()?;
+    readme_benchmarks::parse_puzzle_title(&description)
+}
+
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{commands::solve::verbosity_flag, readme_benchmarks, Day};
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -74,7 +117,12 @@ mod child_commands {
     };
 
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        verbosity: u8,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
@@ -87,12 +135,17 @@ mod child_commands {
             args.push("--release");
         }
 
+        args.push("--");
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        if let Some(flag) = verbosity_flag(verbosity) {
+            args.push(flag);
+        }
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
@@ -125,13 +178,38 @@ mod child_commands {
         Ok(output)
     }
 
+    /// Lines a solution printed to stdout itself, rather than through the runner. Answers and
+    /// failure reasons on lines of their own follow a `▼` or `✖` line and are not stray.
+    pub fn stray_lines(output: &[String]) -> Vec<&str> {
+        let mut in_answer = false;
+        output
+            .iter()
+            .filter(|line| {
+                // progress lines are overwritten after a carriage return
+                let shown = line.rsplit('\r').next().unwrap_or_default();
+                if shown.starts_with("Parse:") || shown.starts_with("Part ") {
+                    in_answer = shown.contains('▼') || shown.contains('✖');
+                    return false;
+                }
+                !in_answer
+                    && !line.trim().is_empty()
+                    && !line.starts_with("Submitting")
+                    && !line.starts_with("Title: ")
+            })
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// The title a [`Solution`](crate::template::Solution) with a `TITLE` prints first.
+    pub fn parse_title(output: &[String]) -> Option<String> {
+        output
+            .iter()
+            .find_map(|line| line.strip_prefix("Title: "))
+            .map(|title| title.trim().to_string())
+    }
+
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+        let mut timings = super::Timings::empty(day);
 
         output
             .iter()
@@ -146,13 +224,23 @@ mod child_commands {
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, timing_str, nanos, parse_samples(l)))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, timing_str, nanos, samples)| {
+                if part.contains("Parse") {
+                    // parsing happens inside the parts as well, so it does not count towards the total.
+                    timings.parse = Some(timing_str.into());
+                    return;
+                }
+
                 if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.part_1_nanos = nanos;
+                    timings.part_1_samples = samples;
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.part_2_nanos = nanos;
+                    timings.part_2_samples = samples;
                 }
 
                 timings.total_nanos += nanos;
@@ -161,27 +249,27 @@ mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    fn parse_samples(line: &str) -> Option<u128> {
+        line.split(" samples)")
+            .next()?
+            .rsplit('@')
+            .next()?
+            .trim()
+            .parse()
+            .ok()
     }
 
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        let parsed_timing = readme_benchmarks::parse_duration(str_timing)?;
 
         Some((str_timing, parsed_timing))
     }
@@ -202,7 +290,7 @@ mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_exec_time, parse_title, stray_lines};
 
         use crate::day;
 
@@ -219,6 +307,23 @@ mod child_commands {
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
             assert_eq!(res.part_1.unwrap(), "74.13ns");
             assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.part_1_samples, Some(100000));
+            assert_eq!(res.part_2_samples, Some(99999));
+        }
+
+        #[test]
+        fn test_parse_line() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: ✔ (1.5µs @ 2000 samples)".into(),
+                    "Part 1: 0 (2ms @ 100 samples)".into(),
+                    "Part 2: 10 (3ms @ 10 samples)".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.parse.unwrap(), "1.5µs");
+            assert_approx_eq!(res.part_1_nanos, 2000000_f64);
+            assert_approx_eq!(res.total_nanos, 5000000_f64);
         }
 
         #[test]
@@ -250,5 +355,34 @@ mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn test_parse_title() {
+            let output: Vec<String> = ["Title: Aplenty", "Part 1: 42 (2ms @ 100 samples)"]
+                .map(String::from)
+                .to_vec();
+            assert_eq!(parse_title(&output), Some("Aplenty".to_string()));
+            assert_eq!(parse_title(&output[1..]), None);
+        }
+
+        #[test]
+        fn test_stray_lines() {
+            let output: Vec<String> = [
+                "Title: Aplenty",
+                "(1, 2)",
+                "Parse: ✔ > benching\rParse: ✔ (1.5µs @ 2000 samples)",
+                "Part 1: 42 > benchingstep 1",
+                "step 2",
+                "\rPart 1: 42 (2ms @ 100 samples)",
+                "Part 2: ▼ (3ms @ 10 samples)",
+                "#.#",
+                "Part 2: ✖             ",
+                "no answer",
+                "",
+            ]
+            .map(String::from)
+            .to_vec();
+            assert_eq!(stray_lines(&output), vec!["(1, 2)", "step 2"]);
+        }
     }
 }
@@ -1,6 +1,6 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
     process,
 };
 
@@ -13,8 +13,36 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
+const BENCHES_PATH: &str = "benches/days.rs";
+const BENCHES_MARKER: &str = "    // new days go above this line";
+
+/// Adds the day to the list of days benched by `cargo bench`.
+fn register_bench(day: Day) -> Result<(), io::Error> {
+    let benches = fs::read_to_string(BENCHES_PATH)?;
+    let entry = format!(
+        "    {} => day{day}: \"../src/bin/{day}.rs\",\n",
+        day.into_inner()
+    );
+    if benches.contains(&entry) {
+        return Ok(());
+    }
+    let Some(index) = benches.find(BENCHES_MARKER) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "list of days not found",
+        ));
+    };
+    let mut benches = benches;
+    benches.insert_str(index, &entry);
+    fs::write(BENCHES_PATH, benches)
+}
+
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)
 }
 
 pub fn handle(day: Day) {
@@ -44,6 +72,15 @@ pub fn handle(day: Day) {
         }
     }
 
+    match register_bench(day) {
+        Ok(()) => {
+            println!("Added day to \"{BENCHES_PATH}\"");
+        }
+        Err(e) => {
+            eprintln!("Failed to add day to benchmarks: {e}");
+        }
+    }
+
     match create_file(&input_path) {
         Ok(_) => {
             println!("Created empty input file \"{}\"", &input_path);
@@ -1,14 +1,44 @@
-use std::process::{Command, Stdio};
+use std::io::{self, BufRead, BufReader, IsTerminal, Write};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{
+    aoc_cli,
+    commands::{all, download},
+    read_file, Day,
+};
+
+/// What `solve` passes on to the solution besides the day.
+#[derive(Debug, Default)]
+pub struct SolveOptions {
+    /// Run against the example input.
+    pub example: bool,
+    /// Play the frames of the solution, built with the `visualize` feature.
+    pub visualize: bool,
+    /// Save the frames of the solution to this directory.
+    pub export: Option<String>,
+    /// Comma-separated formats to export, all if not given.
+    pub export_formats: Option<String>,
+    pub params: Vec<(String, String)>,
+    /// Debug output of the solution, 1 for `-v` and 2 for `-vv`.
+    pub verbosity: u8,
+}
+
+pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>, options: SolveOptions) {
+    if !options.example {
+        ensure_input(day);
+    }
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if release {
         cmd_args.push("--release".to_string());
     }
 
+    if options.visualize || options.export.is_some() {
+        cmd_args.push("--features".to_string());
+        cmd_args.push("visualize".to_string());
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -20,12 +50,104 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push("--time".to_string());
     }
 
+    if options.example {
+        cmd_args.push("--example".to_string());
+    }
+
+    if let Some(flag) = verbosity_flag(options.verbosity) {
+        cmd_args.push(flag.to_string());
+    }
+
+    if options.visualize {
+        cmd_args.push("--visualize".to_string());
+    }
+
+    if let Some(dir) = options.export {
+        cmd_args.push("--export".to_string());
+        cmd_args.push(dir);
+    }
+
+    if let Some(formats) = options.export_formats {
+        cmd_args.push("--export-formats".to_string());
+        cmd_args.push(formats);
+    }
+
+    for (name, value) in &options.params {
+        cmd_args.push("--param".to_string());
+        cmd_args.push(format!("{name}={value}"));
+    }
+
+    // timed runs forward stdout line by line to check it for stray output, animations need
+    // the terminal itself.
+    let check_output = time && !options.visualize;
+    let stdout = if check_output {
+        Stdio::piped()
+    } else {
+        Stdio::inherit()
+    };
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
-        .stdout(Stdio::inherit())
+        .stdout(stdout)
         .stderr(Stdio::inherit())
         .spawn()
         .unwrap();
 
+    if let Some(stdout) = cmd.stdout.take() {
+        let output: Vec<String> = BufReader::new(stdout)
+            .lines()
+            .map_while(Result::ok)
+            .inspect(|line| println!("{line}"))
+            .collect();
+        all::warn_stray_lines(day, &output);
+    }
+
     cmd.wait().unwrap();
 }
+
+/// The flag passing `verbosity` on to a solution binary.
+pub fn verbosity_flag(verbosity: u8) -> Option<&'static str> {
+    match verbosity {
+        0 => None,
+        1 => Some("-v"),
+        _ => Some("-vv"),
+    }
+}
+
+/// Offers to download the input of `day` through aoc-cli if it is missing or empty, and
+/// exits if it is still not there.
+fn ensure_input(day: Day) {
+    let Err(err) = read_file("inputs", day) else {
+        return;
+    };
+    eprintln!("{err}");
+    if !err.is_missing_or_empty() {
+        process::exit(1);
+    }
+
+    let can_offer = io::stdin().is_terminal() && aoc_cli::check().is_ok();
+    if can_offer
+        && confirm(&format!(
+            "Download the input of day {day} with aoc-cli? [Y/n] "
+        ))
+    {
+        download::handle(day);
+        if let Err(err) = read_file("inputs", day) {
+            eprintln!("{err}");
+            process::exit(1);
+        }
+    } else {
+        eprintln!("Download it with `cargo download {day}`, or run the example with `--example`.");
+        process::exit(1);
+    }
+}
+
+fn confirm(question: &str) -> bool {
+    eprint!("{question}");
+    let _ = io::stderr().flush();
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "" | "y" | "yes")
+}
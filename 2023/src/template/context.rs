@@ -0,0 +1,185 @@
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::visualize::{Export, ExportFormat, Visualizer, VisualizerOptions};
+
+/// What a solution knows about the run besides its input: whether the input is an example,
+/// and named parameters for constants that differ between the examples and the real input.
+///
+/// Parts opt in by taking a second argument, e.g. `pub fn part_one(input: &str, ctx: &Context)`.
+/// Parameters are set with `cargo solve 21 --param steps=64`, or in tests:
+///
+/// ```
+/// # use advent_of_code::template::Context;
+/// let ctx = Context::example().with_param("steps", 6);
+/// assert!(ctx.is_example());
+/// assert_eq!(ctx.param_or("steps", ctx.example_or(6, 64)), 6);
+/// assert_eq!(ctx.used_params(), ["steps"]);
+/// assert_eq!(Context::default().param_or("steps", 64), 64);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Context {
+    example: bool,
+    visualize: bool,
+    verbosity: u8,
+    export: Option<Export>,
+    params: HashMap<String, String>,
+    /// The names of the set parameters read through [`Context::param`].
+    used: RefCell<BTreeSet<String>>,
+}
+
+impl Context {
+    /// The context of a run on the real input without parameters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The context of a run on an example input.
+    pub fn example() -> Self {
+        Context {
+            example: true,
+            ..Self::default()
+        }
+    }
+
+    /// Reads `--example`, `-v`, `-vv`, `--visualize`, `--export <dir>`, `--export-formats <list>` and any
+    /// number of `--param <name>=<value>` from the arguments of the solution binary. Timed
+    /// runs are never visualized or exported.
+    pub fn from_args() -> Result<Self, Box<dyn Error>> {
+        let mut ctx = Context::new();
+        let mut timed = false;
+        let mut export_dir = None;
+        let mut export_formats = ExportFormat::ALL.to_vec();
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--example" => ctx.example = true,
+                "-v" => ctx.verbosity = ctx.verbosity.max(1),
+                "-vv" => ctx.verbosity = 2,
+                "--visualize" => ctx.visualize = true,
+                "--time" => timed = true,
+                "--export" => export_dir = args.next().map(PathBuf::from),
+                "--export-formats" => {
+                    export_formats = args
+                        .next()
+                        .unwrap_or_default()
+                        .split(',')
+                        .map(str::parse)
+                        .collect::<Result<_, _>>()?;
+                }
+                "--param" => {
+                    let param = args.next().unwrap_or_default();
+                    let (name, value) = parse_param(&param)?;
+                    ctx.params.insert(name, value);
+                }
+                _ => {}
+            }
+        }
+        if !timed {
+            ctx.export = export_dir.map(|dir| Export {
+                dir,
+                formats: export_formats,
+            });
+        } else {
+            ctx.visualize = false;
+        }
+        Ok(ctx)
+    }
+
+    #[must_use]
+    pub fn with_param(mut self, name: &str, value: impl Display) -> Self {
+        self.params.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn is_example(&self) -> bool {
+        self.example
+    }
+
+    /// The parameters that were set and read by the solution so far. Parameters that only
+    /// matter for side effects, e.g. the name of a file to write, may be set without
+    /// changing the answer as long as the parts do not read them.
+    pub fn used_params(&self) -> Vec<String> {
+        self.used.borrow().iter().cloned().collect()
+    }
+
+    /// How much debug output was asked for, see [`crate::template::debug`].
+    pub fn verbosity(&self) -> u8 {
+        self.verbosity
+    }
+
+    /// A [`Visualizer`] playing the frames of this run if `--visualize` was passed and saving
+    /// them if `--export` was, at the frame rate of the `fps` parameter.
+    pub fn visualizer(&self) -> Visualizer {
+        if self.visualize || self.export.is_some() {
+            Visualizer::new(&VisualizerOptions {
+                play: self.visualize,
+                fps: self.setting("fps").unwrap_or(20),
+                export: self.export.clone(),
+            })
+        } else {
+            Visualizer::disabled()
+        }
+    }
+
+    /// Picks the value for the examples or the one for the real input.
+    pub fn example_or<T>(&self, example: T, real: T) -> T {
+        if self.example {
+            example
+        } else {
+            real
+        }
+    }
+
+    /// The parameter `name`, if it was set.
+    ///
+    /// # Panics
+    /// If the value does not parse as `T`, as the solution cannot go on without it.
+    pub fn param<T: FromStr>(&self, name: &str) -> Option<T> {
+        let value = self.setting(name)?;
+        self.used.borrow_mut().insert(name.to_string());
+        Some(value)
+    }
+
+    /// The parameter `name`, or `default` if it was not set.
+    pub fn param_or<T: FromStr>(&self, name: &str, default: T) -> T {
+        self.param(name).unwrap_or(default)
+    }
+
+    /// Like [`Context::param`], for parameters of the runner that cannot change the answer.
+    fn setting<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.params.get(name).map(|value| {
+            value
+                .parse()
+                .unwrap_or_else(|_| panic!("invalid value \"{value}\" for parameter \"{name}\""))
+        })
+    }
+}
+
+/// Splits a `<name>=<value>` parameter.
+pub fn parse_param(param: &str) -> Result<(String, String), ParamError> {
+    match param.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_string(), value.to_string())),
+        _ => Err(ParamError(param.to_string())),
+    }
+}
+
+#[derive(Debug)]
+pub struct ParamError(String);
+
+impl Display for ParamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "\"{}\" is not a parameter, expected <name>=<value>.",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParamError {}
@@ -0,0 +1,84 @@
+/// Debug output of solutions, shown with `cargo solve DD -v` (or `-vv` for more detail).
+///
+/// Messages go to stderr, so they never mix with the results `cargo all` reads from stdout,
+/// and are dropped while the runner benches a solution, so they neither flood the terminal
+/// nor skew the timings.
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+static SILENCED: AtomicBool = AtomicBool::new(false);
+
+/// Sets how much debug output is shown: 0 for none, 1 for `-v`, 2 for `-vv`.
+pub fn set_verbosity(level: u8) {
+    VERBOSITY.store(level, Ordering::Relaxed);
+}
+
+pub fn verbosity() -> u8 {
+    VERBOSITY.load(Ordering::Relaxed)
+}
+
+/// Whether messages of `level` are shown right now.
+#[inline]
+pub fn enabled(level: u8) -> bool {
+    level <= verbosity() && !SILENCED.load(Ordering::Relaxed)
+}
+
+/// Drops all debug output until the returned guard is dropped.
+#[must_use]
+pub fn silence() -> Silenced {
+    Silenced(SILENCED.swap(true, Ordering::Relaxed))
+}
+
+/// Restores the previous debug output when dropped, see [`silence`].
+pub struct Silenced(bool);
+
+impl Drop for Silenced {
+    fn drop(&mut self) {
+        SILENCED.store(self.0, Ordering::Relaxed);
+    }
+}
+
+/// Prints a debug message to stderr, like `eprintln!`, if the verbosity allows it.
+///
+/// Messages are shown from `-v` on, or from `-vv` with a leading `2;`. The arguments are
+/// not evaluated otherwise.
+///
+/// ```
+/// # use advent_of_code::aoc_debug;
+/// let steps = 3;
+/// aoc_debug!("took {steps} steps");
+/// aoc_debug!(2; "visited {:?}", vec![1, 2, 3]);
+/// ```
+#[macro_export]
+macro_rules! aoc_debug {
+    ($level:literal; $($arg:tt)+) => {
+        if $crate::template::debug::enabled($level) {
+            eprintln!($($arg)+);
+        }
+    };
+    ($($arg:tt)+) => {
+        $crate::aoc_debug!(1; $($arg)+)
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{enabled, set_verbosity, silence};
+
+    #[test]
+    fn levels_and_silence() {
+        set_verbosity(1);
+        assert!(enabled(1));
+        assert!(!enabled(2));
+        {
+            let _quiet = silence();
+            assert!(!enabled(1));
+            let _nested = silence();
+        }
+        assert!(enabled(1));
+        set_verbosity(0);
+        assert!(!enabled(1));
+    }
+}
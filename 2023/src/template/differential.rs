@@ -0,0 +1,37 @@
+/// Defines a differential test: a solution checked against a brute-force reference on
+/// random inputs.
+///
+/// The test runs `$solution` and `$reference`, both taking the input as `&str`, on inputs
+/// generated by the `proptest` strategy `$inputs` and asserts that they agree. If they do
+/// not, the input is shrunk to a minimal one before the test fails. The number of inputs
+/// tried defaults to 256 and can be set with `cases = <n>`.
+///
+/// ```ignore
+/// advent_of_code::differential_test! {
+///     part_one_matches_brute_force: part_one, brute_force, races()
+/// }
+/// ```
+///
+/// The macro expands to `proptest` code in the calling crate, where it is a dev-dependency,
+/// so the library itself does not depend on it.
+#[macro_export]
+macro_rules! differential_test {
+    (
+        $(#[$meta:meta])*
+        $name:ident: $solution:expr, $reference:expr, $inputs:expr $(, cases = $cases:expr)?
+    ) => {
+        ::proptest::proptest! {
+            #![proptest_config(::proptest::test_runner::Config {
+                $( cases: $cases, )?
+                ..::proptest::test_runner::Config::default()
+            })]
+
+            $(#[$meta])*
+            #[test]
+            fn $name(input in $inputs) {
+                let input: &str = &input;
+                ::proptest::prop_assert_eq!(($solution)(input), ($reference)(input));
+            }
+        }
+    };
+}
@@ -0,0 +1,127 @@
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::template::Day;
+
+/// Why an input file could not be used.
+#[derive(Debug)]
+pub enum InputError {
+    /// The file does not exist, e.g. as the day was never scaffolded.
+    Missing(PathBuf),
+    /// The file has nothing but whitespace, e.g. as `cargo scaffold` created it but the
+    /// input was never downloaded.
+    Empty(PathBuf),
+    NotUtf8(PathBuf),
+    Io(PathBuf, io::Error),
+}
+
+impl InputError {
+    pub fn path(&self) -> &Path {
+        match self {
+            InputError::Missing(path)
+            | InputError::Empty(path)
+            | InputError::NotUtf8(path)
+            | InputError::Io(path, _) => path,
+        }
+    }
+
+    /// Whether the file still needs to be filled in, rather than being broken.
+    pub fn is_missing_or_empty(&self) -> bool {
+        matches!(self, InputError::Missing(_) | InputError::Empty(_))
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Missing(path) => write!(f, "\"{}\" does not exist.", path.display()),
+            InputError::Empty(path) => write!(f, "\"{}\" is empty.", path.display()),
+            InputError::NotUtf8(path) => write!(f, "\"{}\" is not UTF-8 text.", path.display()),
+            InputError::Io(path, err) => write!(f, "cannot read \"{}\": {err}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// The path of the file of `day` in `data/<folder>`, with an optional part suffix.
+pub fn input_path(folder: &str, day: Day, part: Option<u8>) -> PathBuf {
+    let name = match part {
+        Some(part) => format!("{day}-{part}.txt"),
+        None => format!("{day}.txt"),
+    };
+    Path::new("data").join(folder).join(name)
+}
+
+/// Reads the file of `day` in `data/<folder>`, see [`normalize`].
+pub fn read_file(folder: &str, day: Day) -> Result<String, InputError> {
+    read_input(&input_path(folder, day, None))
+}
+
+/// Reads the file of `day` in `data/<folder>` with a part suffix, e.g. `01-2.txt`, see
+/// [`normalize`].
+pub fn read_file_part(folder: &str, day: Day, part: u8) -> Result<String, InputError> {
+    read_input(&input_path(folder, day, Some(part)))
+}
+
+fn read_input(path: &Path) -> Result<String, InputError> {
+    let bytes = fs::read(path).map_err(|err| match err.kind() {
+        io::ErrorKind::NotFound => InputError::Missing(path.to_path_buf()),
+        _ => InputError::Io(path.to_path_buf(), err),
+    })?;
+    let text = String::from_utf8(bytes).map_err(|_| InputError::NotUtf8(path.to_path_buf()))?;
+    if text.trim().is_empty() {
+        return Err(InputError::Empty(path.to_path_buf()));
+    }
+    Ok(normalize(&text))
+}
+
+/// Turns CRLF line endings into LF and ends the text with exactly one newline, so that
+/// solutions see the same input whichever way the file was saved.
+///
+/// ```
+/// # use advent_of_code::template::normalize;
+/// assert_eq!(normalize("1 2\r\n3 4\r\n\r\n"), "1 2\n3 4\n");
+/// assert_eq!(normalize("1 2\n3 4"), "1 2\n3 4\n");
+/// ```
+pub fn normalize(text: &str) -> String {
+    let mut text = text.replace("\r\n", "\n");
+    text.truncate(text.trim_end_matches('\n').len());
+    text.push('\n');
+    text
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::fs;
+
+    use super::{read_input, InputError};
+
+    #[test]
+    fn errors() {
+        let dir = std::env::temp_dir().join("aoc-input-test");
+        fs::create_dir_all(&dir).unwrap();
+
+        let missing = dir.join("missing.txt");
+        assert!(matches!(read_input(&missing), Err(InputError::Missing(_))));
+
+        let empty = dir.join("empty.txt");
+        fs::write(&empty, "\n\n").unwrap();
+        let err = read_input(&empty).unwrap_err();
+        assert!(matches!(err, InputError::Empty(_)));
+        assert!(err.is_missing_or_empty());
+        assert_eq!(err.path(), empty);
+
+        let binary = dir.join("binary.txt");
+        fs::write(&binary, [0xff, 0xfe]).unwrap();
+        assert!(matches!(read_input(&binary), Err(InputError::NotUtf8(_))));
+
+        let text = dir.join("text.txt");
+        fs::write(&text, "a\r\nb").unwrap();
+        assert_eq!(read_input(&text).unwrap(), "a\nb\n");
+    }
+}
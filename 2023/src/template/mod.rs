@@ -1,46 +1,60 @@
-use std::{env, fs};
-
 pub mod aoc_cli;
 pub mod commands;
+mod context;
 mod day;
+pub mod debug;
+mod differential;
+mod input;
 pub mod readme_benchmarks;
 pub mod runner;
+mod solution;
 
+pub use context::*;
 pub use day::*;
+pub use input::*;
+pub use solution::*;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
-#[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
-}
-
-/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
-#[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
-}
-
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Alternatively, `parse = <fn>` names the parser of the solution, which is then timed on its own.
+/// `export = <fn>` names a function writing files asked for with `--param`, called once before
+/// the parts so that benching them does not repeat it, see [`runner::run_export`]. Both can be
+/// given, `parse` first.
+///
+/// Parts either take the input alone or the input and a [`Context`], built from the
+/// `--example` and `--param` arguments of the binary. `-v` and `-vv` turn on the messages of
+/// [`aoc_debug!`](crate::aoc_debug).
+///
+/// Instead of free functions, `solution = <type>` runs a type implementing [`Solution`].
 #[macro_export]
 macro_rules! solution {
+    (solution = $solution:ty) => {
+        /// The current day.
+        const DAY: $crate::template::Day =
+            <$solution as $crate::template::Solution>::DAY;
+
+        fn main() {
+            let (ctx, input) = $crate::template::runner::setup(DAY);
+            $crate::template::runner::run_solution::<$solution>(&input, &ctx);
+        }
+    };
     ($day:expr) => {
         $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, export = $export:expr) => {
+        $crate::solution!(@impl $day, export $export, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr, export = $export:expr) => {
+        $crate::solution!(@impl $day, parse $parse, export $export, [part_one, 1] [part_two, 2]);
+    };
     ($day:expr, 1) => {
         $crate::solution!(@impl $day, [part_one, 1]);
     };
@@ -48,14 +62,16 @@ macro_rules! solution {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, $( parse $parse:expr, )? $( export $export:expr, )? $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let (ctx, input) = setup(DAY);
+            $( run_parse($parse, input.as_str()); )?
+            $( run_export($export, &input, &ctx); )?
+            $( run_part($func, &input, &ctx, DAY, $part); )*
         }
     };
 }
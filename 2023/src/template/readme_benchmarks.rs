@@ -1,11 +1,17 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{cmp::Ordering, fmt::Display, fs, io, str::FromStr, time::Duration};
 
 use crate::template::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+/// Appended to timings that were kept from a previous run because the last run did not produce them.
+static STALE_MARKER: &str = "†";
+
+/// Width of the bar chart column, in characters.
+const CHART_WIDTH: usize = 20;
+
 #[derive(Debug)]
 pub enum Error {
     Parser(String),
@@ -21,11 +27,311 @@ impl From<std::io::Error> for Error {
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
+    pub title: Option<String>,
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_nanos: f64,
+    pub part_2_nanos: f64,
+    pub part_1_samples: Option<u128>,
+    pub part_2_samples: Option<u128>,
+    pub part_1_stale: bool,
+    pub part_2_stale: bool,
     pub total_nanos: f64,
 }
 
+impl Timings {
+    /// Timings of a day that did not produce any output, e.g. because it is not solved yet.
+    #[must_use]
+    pub fn empty(day: Day) -> Self {
+        Self {
+            day,
+            title: None,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            part_1_nanos: 0_f64,
+            part_2_nanos: 0_f64,
+            part_1_samples: None,
+            part_2_samples: None,
+            part_1_stale: false,
+            part_2_stale: false,
+            total_nanos: 0_f64,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.part_1.is_none() && self.part_2.is_none()
+    }
+}
+
+/// An optional column of the benchmarking table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    /// Puzzle title, read from `data/puzzles/DD.md`.
+    Title,
+    /// Time spent in the parser, for days that declare one in `solution!`.
+    Parse,
+    /// Number of samples taken for each part.
+    Samples,
+    /// Combined time of both parts.
+    Total,
+    /// Share of the day in the total runtime.
+    Share,
+    /// Text bar chart of the day's runtime.
+    Chart,
+}
+
+impl Column {
+    const ALL: [Column; 6] = [
+        Column::Title,
+        Column::Parse,
+        Column::Samples,
+        Column::Total,
+        Column::Share,
+        Column::Chart,
+    ];
+
+    fn position(self) -> usize {
+        Self::ALL.iter().position(|&c| c == self).unwrap()
+    }
+}
+
+/// Order in which days are listed in the benchmarking table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Day,
+    /// Slowest day first.
+    Runtime,
+}
+
+/// Layout of the benchmarking table, set via flags on `cargo time`.
+#[derive(Debug, Clone, Default)]
+pub struct TableOptions {
+    pub columns: Vec<Column>,
+    pub part_totals: bool,
+    pub sort: SortOrder,
+}
+
+impl TableOptions {
+    fn has(&self, column: Column) -> bool {
+        self.columns.contains(&column)
+    }
+}
+
+/// An error which can be returned when parsing a table option.
+#[derive(Debug)]
+pub struct TableOptionError(String);
+
+impl std::error::Error for TableOptionError {}
+
+impl Display for TableOptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown table option `{}`", self.0)
+    }
+}
+
+impl FromStr for Column {
+    type Err = TableOptionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "title" => Ok(Column::Title),
+            "parse" => Ok(Column::Parse),
+            "samples" => Ok(Column::Samples),
+            "total" => Ok(Column::Total),
+            "share" => Ok(Column::Share),
+            "chart" => Ok(Column::Chart),
+            x => Err(TableOptionError(x.into())),
+        }
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = TableOptionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "day" => Ok(SortOrder::Day),
+            "runtime" => Ok(SortOrder::Runtime),
+            x => Err(TableOptionError(x.into())),
+        }
+    }
+}
+
+/// Parses a comma-separated list of columns, e.g. `title,share,chart`. `all` selects every column.
+pub fn parse_columns(s: &str) -> Result<Vec<Column>, TableOptionError> {
+    if s.trim() == "all" {
+        return Ok(Column::ALL.to_vec());
+    }
+
+    let mut columns = s
+        .split(',')
+        .map(str::parse)
+        .collect::<Result<Vec<Column>, _>>()?;
+
+    // columns are always rendered in the same order, regardless of how they were passed.
+    columns.sort_by_key(|c| c.position());
+    columns.dedup();
+    Ok(columns)
+}
+
+/// Extracts the puzzle title from a description downloaded by aoc-cli, e.g. `--- Day 1: Trebuchet?! ---`.
+#[must_use]
+pub fn parse_puzzle_title(description: &str) -> Option<String> {
+    let line = description.lines().find(|l| l.contains("Day "))?;
+    let (_, title) = line.split_once(": ")?;
+    let title = title
+        .trim()
+        .trim_end_matches('-')
+        .trim()
+        .trim_end_matches('\\')
+        .trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Parses a duration as printed by the runner, e.g. `74.13µs`, into nanoseconds.
+#[must_use]
+pub fn parse_duration(s: &str) -> Option<f64> {
+    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+        s.split(postfix).next()?.parse().ok()
+    }
+
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+/// Splits a markdown table row into its cells, honoring escaped pipes.
+fn split_row(row: &str) -> Vec<String> {
+    let mut cells = vec![];
+    let mut cell = String::new();
+    let mut chars = row.trim().trim_start_matches('|').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cell.push('|');
+                chars.next();
+            }
+            '|' => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            c => cell.push(c),
+        }
+    }
+
+    cells
+}
+
+/// Reads a timing cell such as `` `10ms`† `` into its value and stale flag.
+fn parse_timing_cell(cell: &str) -> (Option<String>, bool) {
+    let stale = cell.ends_with(STALE_MARKER);
+    let value = cell.trim_end_matches(STALE_MARKER).trim().trim_matches('`');
+    let value = (!value.is_empty() && value != "-").then(|| value.to_string());
+    let stale = stale && value.is_some();
+    (value, stale)
+}
+
+/// Reads the rows of a table previously written by [`construct_table`].
+fn parse_table(table: &str) -> Vec<Timings> {
+    let mut headers: Vec<String> = vec![];
+    let mut timings = vec![];
+
+    for line in table.lines().filter(|l| l.starts_with('|')) {
+        let cells = split_row(line);
+
+        if cells.first().is_some_and(|c| c == "Day") {
+            headers = cells;
+            continue;
+        }
+
+        let Some(day) = cells
+            .first()
+            .and_then(|c| c.strip_prefix("[Day "))
+            .and_then(|c| c.split(']').next())
+            .and_then(|c| c.parse().ok())
+        else {
+            continue;
+        };
+
+        let mut timing = Timings::empty(day);
+
+        for (header, cell) in headers.iter().zip(&cells) {
+            match header.as_str() {
+                "Title" => timing.title = (cell != "-").then(|| cell.clone()),
+                "Parse" => timing.parse = parse_timing_cell(cell).0,
+                "Part 1" => (timing.part_1, timing.part_1_stale) = parse_timing_cell(cell),
+                "Part 2" => (timing.part_2, timing.part_2_stale) = parse_timing_cell(cell),
+                "Samples" => {
+                    let mut samples = cell.split('/').map(|s| s.trim().parse().ok());
+                    timing.part_1_samples = samples.next().flatten();
+                    timing.part_2_samples = samples.next().flatten();
+                }
+                _ => {}
+            }
+        }
+
+        timing.part_1_nanos = timing
+            .part_1
+            .as_deref()
+            .and_then(parse_duration)
+            .unwrap_or(0_f64);
+        timing.part_2_nanos = timing
+            .part_2
+            .as_deref()
+            .and_then(parse_duration)
+            .unwrap_or(0_f64);
+        timing.total_nanos = timing.part_1_nanos + timing.part_2_nanos;
+
+        timings.push(timing);
+    }
+
+    timings
+}
+
+/// Merges the timings of the last run into the rows of the existing table.
+/// Parts that the last run did not produce keep their previous value and are marked as stale,
+/// days that were not part of the last run are kept as they are.
+fn merge_timings(existing: Vec<Timings>, fresh: Vec<Timings>) -> Vec<Timings> {
+    let mut merged = existing;
+
+    for mut timing in fresh {
+        let Some(previous) = merged.iter().position(|t| t.day == timing.day) else {
+            if !timing.is_empty() {
+                merged.push(timing);
+            }
+            continue;
+        };
+
+        let previous = merged.remove(previous);
+
+        if timing.part_1.is_none() && previous.part_1.is_some() {
+            timing.part_1 = previous.part_1;
+            timing.part_1_nanos = previous.part_1_nanos;
+            timing.part_1_samples = previous.part_1_samples;
+            timing.part_1_stale = true;
+        }
+        if timing.part_2.is_none() && previous.part_2.is_some() {
+            timing.part_2 = previous.part_2;
+            timing.part_2_nanos = previous.part_2_nanos;
+            timing.part_2_samples = previous.part_2_samples;
+            timing.part_2_stale = true;
+        }
+        timing.title = timing.title.or(previous.title);
+        timing.parse = timing.parse.or(previous.parse);
+        timing.total_nanos = timing.part_1_nanos + timing.part_2_nanos;
+
+        merged.push(timing);
+    }
+
+    merged.sort_by_key(|t| t.day);
+    merged
+}
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
@@ -58,104 +364,260 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn chart_bar(nanos: f64, max_nanos: f64) -> String {
+    if max_nanos <= 0.0 {
+        return String::new();
+    }
+    let width = ((nanos / max_nanos) * CHART_WIDTH as f64).round() as usize;
+    "█".repeat(width.max(1))
+}
+
+fn format_share(nanos: f64, total_nanos: f64) -> String {
+    if total_nanos <= 0.0 {
+        return "-".into();
+    }
+    format!("{:.1}%", nanos / total_nanos * 100.0)
+}
+
+fn format_samples(timing: &Timings) -> String {
+    [timing.part_1_samples, timing.part_2_samples]
+        .iter()
+        .map(|s| s.map_or_else(|| "-".into(), |s| s.to_string()))
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
+fn format_timing(timing: Option<&str>, stale: bool) -> String {
+    let marker = if stale { STALE_MARKER } else { "" };
+    format!("`{}`{marker}", timing.unwrap_or("-"))
+}
+
+fn table_row(cells: &[String]) -> String {
+    format!("| {} |", cells.join(" | "))
+}
+
+fn construct_table(
+    prefix: &str,
+    mut timings: Vec<Timings>,
+    total_millis: f64,
+    options: &TableOptions,
+) -> String {
     let header = format!("{prefix} Benchmarks");
 
+    let mut headers = vec!["Day".to_string()];
+    if options.has(Column::Title) {
+        headers.push("Title".into());
+    }
+    if options.has(Column::Parse) {
+        headers.push("Parse".into());
+    }
+    headers.push("Part 1".into());
+    headers.push("Part 2".into());
+    for (column, name) in [
+        (Column::Samples, "Samples"),
+        (Column::Total, "Total"),
+        (Column::Share, "Share"),
+        (Column::Chart, "Chart"),
+    ] {
+        if options.has(column) {
+            headers.push(name.into());
+        }
+    }
+
     let mut lines: Vec<String> = vec![
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        table_row(&headers),
+        format!("| {}  |", vec![":---:"; headers.len()].join(" | ")),
     ];
 
-    for timing in timings {
+    if options.sort == SortOrder::Runtime {
+        timings.sort_by(|a, b| {
+            b.total_nanos
+                .partial_cmp(&a.total_nanos)
+                .unwrap_or(Ordering::Equal)
+        });
+    }
+
+    let total_nanos = timings.iter().map(|t| t.total_nanos).sum::<f64>();
+    let max_nanos = timings.iter().map(|t| t.total_nanos).fold(0.0, f64::max);
+
+    for timing in &timings {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        let mut cells = vec![format!("[Day {}]({})", timing.day.into_inner(), path)];
+
+        if options.has(Column::Title) {
+            cells.push(timing.title.as_deref().unwrap_or("-").replace('|', "\\|"));
+        }
+        if options.has(Column::Parse) {
+            cells.push(format!("`{}`", timing.parse.as_deref().unwrap_or("-")));
+        }
+        cells.push(format_timing(timing.part_1.as_deref(), timing.part_1_stale));
+        cells.push(format_timing(timing.part_2.as_deref(), timing.part_2_stale));
+        if options.has(Column::Samples) {
+            cells.push(format_samples(timing));
+        }
+        if options.has(Column::Total) {
+            cells.push(format!("`{}`", format_nanos(timing.total_nanos)));
+        }
+        if options.has(Column::Share) {
+            cells.push(format_share(timing.total_nanos, total_nanos));
+        }
+        if options.has(Column::Chart) {
+            cells.push(format!("`{}`", chart_bar(timing.total_nanos, max_nanos)));
+        }
+
+        lines.push(table_row(&cells));
+    }
+
+    if options.part_totals {
+        let part_1 = timings.iter().map(|t| t.part_1_nanos).sum::<f64>();
+        let part_2 = timings.iter().map(|t| t.part_2_nanos).sum::<f64>();
+
+        let mut cells = vec!["**Total**".to_string()];
+        if options.has(Column::Title) {
+            cells.push(String::new());
+        }
+        if options.has(Column::Parse) {
+            cells.push(String::new());
+        }
+        cells.push(format!("`{}`", format_nanos(part_1)));
+        cells.push(format!("`{}`", format_nanos(part_2)));
+        if options.has(Column::Samples) {
+            cells.push(String::new());
+        }
+        if options.has(Column::Total) {
+            cells.push(format!("`{}`", format_nanos(total_nanos)));
+        }
+        if options.has(Column::Share) {
+            cells.push(format_share(total_nanos, total_nanos));
+        }
+        if options.has(Column::Chart) {
+            cells.push(String::new());
+        }
+
+        lines.push(table_row(&cells));
     }
 
     lines.push(String::new());
+    if timings.iter().any(|t| t.part_1_stale || t.part_2_stale) {
+        lines.push(format!(
+            "{STALE_MARKER} kept from a previous run, the last run did not produce this timing."
+        ));
+        lines.push(String::new());
+    }
     lines.push(format!("**Total: {total_millis:.2}ms**"));
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+fn update_content(
+    s: &mut String,
+    timings: Vec<Timings>,
+    total_millis: f64,
+    options: &TableOptions,
+) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+
+    let fresh_nanos = timings.iter().map(|t| t.total_nanos).sum::<f64>();
+    let timings = merge_timings(
+        parse_table(&s[positions.pos_start..positions.pos_end]),
+        timings,
+    );
+    // the total of the last run is extended with the timings kept from previous runs.
+    let kept_nanos = timings.iter().map(|t| t.total_nanos).sum::<f64>() - fresh_nanos;
+    let total_millis = total_millis + kept_nanos.max(0_f64) / 1_000_000_f64;
+
+    let table = construct_table("##", timings, total_millis, options);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+pub fn update(
+    timings: Vec<Timings>,
+    total_millis: f64,
+    options: &TableOptions,
+) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, timings, total_millis, options)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use super::{
+        parse_columns, parse_duration, parse_puzzle_title, update_content, Column, SortOrder,
+        TableOptions, Timings, MARKER,
+    };
+    use crate::{day, template::Day};
+
+    fn mock_timing(day: Day, part_1: &str, part_2: &str, total_nanos: f64) -> Timings {
+        Timings {
+            day,
+            title: None,
+            parse: None,
+            part_1: Some(part_1.into()),
+            part_2: Some(part_2.into()),
+            part_1_nanos: total_nanos / 3.0,
+            part_2_nanos: total_nanos * 2.0 / 3.0,
+            part_1_samples: Some(10),
+            part_2_samples: Some(20),
+            part_1_stale: false,
+            part_2_stale: false,
+            total_nanos,
+        }
+    }
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
-            Timings {
-                day: day!(1),
-                part_1: Some("10ms".into()),
-                part_2: Some("20ms".into()),
-                total_nanos: 3e+10,
-            },
-            Timings {
-                day: day!(2),
-                part_1: Some("30ms".into()),
-                part_2: Some("40ms".into()),
-                total_nanos: 7e+10,
-            },
-            Timings {
-                day: day!(4),
-                part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
-                total_nanos: 9e+10,
-            },
+            mock_timing(day!(1), "10ms", "20ms", 3e+10),
+            mock_timing(day!(2), "30ms", "40ms", 7e+10),
+            mock_timing(day!(4), "40ms", "50ms", 9e+10),
         ]
     }
 
+    fn table_lines(timings: Vec<Timings>, options: &TableOptions) -> Vec<String> {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, options).unwrap();
+        s.lines().map(String::from).collect()
+    }
+
     #[test]
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -163,7 +625,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -183,4 +645,193 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_optional_columns() {
+        let mut timings = get_mock_timings();
+        timings[0].title = Some("Trebuchet?!".into());
+        timings[0].parse = Some("1.0ms".into());
+
+        let options = TableOptions {
+            columns: parse_columns("all").unwrap(),
+            ..TableOptions::default()
+        };
+        let lines = table_lines(timings, &options);
+
+        assert_eq!(
+            lines[3],
+            "| Day | Title | Parse | Part 1 | Part 2 | Samples | Total | Share | Chart |"
+        );
+        assert_eq!(
+            lines[4],
+            "| :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---:  |"
+        );
+        assert_eq!(
+            lines[5],
+            "| [Day 1](./src/bin/01.rs) | Trebuchet?! | `1.0ms` | `10ms` | `20ms` | 10 / 20 | `30.0s` | 15.8% | `███████` |"
+        );
+        assert_eq!(
+            lines[7],
+            "| [Day 4](./src/bin/04.rs) | - | `-` | `40ms` | `50ms` | 10 / 20 | `90.0s` | 47.4% | `████████████████████` |"
+        );
+    }
+
+    #[test]
+    fn sort_by_runtime() {
+        let options = TableOptions {
+            sort: SortOrder::Runtime,
+            ..TableOptions::default()
+        };
+        let lines = table_lines(get_mock_timings(), &options);
+
+        assert!(lines[5].starts_with("| [Day 4]"));
+        assert!(lines[6].starts_with("| [Day 2]"));
+        assert!(lines[7].starts_with("| [Day 1]"));
+    }
+
+    #[test]
+    fn format_part_totals() {
+        let options = TableOptions {
+            columns: vec![Column::Total],
+            part_totals: true,
+            ..TableOptions::default()
+        };
+        let lines = table_lines(get_mock_timings(), &options);
+
+        assert_eq!(lines[8], "| **Total** | `63.3s` | `126.7s` | `190.0s` |");
+    }
+
+    #[test]
+    fn parse_column_list() {
+        assert_eq!(
+            parse_columns("chart,title,chart").unwrap(),
+            vec![Column::Title, Column::Chart]
+        );
+        assert!(parse_columns("title,foo").is_err());
+    }
+
+    #[test]
+    fn parse_title_from_description() {
+        let description =
+            "\\--- Day 1: Trebuchet?! ---\n\nSomething is wrong with global snow production.";
+        assert_eq!(
+            parse_puzzle_title(description),
+            Some("Trebuchet?!".to_string())
+        );
+        assert_eq!(parse_puzzle_title(""), None);
+    }
+
+    fn timed(day: Day, part_1: Option<&str>, part_2: Option<&str>) -> Timings {
+        let mut timing = Timings::empty(day);
+        timing.part_1 = part_1.map(String::from);
+        timing.part_2 = part_2.map(String::from);
+        timing.part_1_nanos = part_1.and_then(parse_duration).unwrap_or(0_f64);
+        timing.part_2_nanos = part_2.and_then(parse_duration).unwrap_or(0_f64);
+        timing.total_nanos = timing.part_1_nanos + timing.part_2_nanos;
+        timing
+    }
+
+    #[test]
+    fn merges_with_existing_rows() {
+        let options = TableOptions::default();
+        let mut s = format!("foo\n{}{}\nbaz", MARKER, MARKER);
+        update_content(
+            &mut s,
+            vec![
+                timed(day!(1), Some("1.0ms"), Some("2.0ms")),
+                timed(day!(2), Some("3.0ms"), Some("4.0ms")),
+            ],
+            10.0,
+            &options,
+        )
+        .unwrap();
+
+        // day 1 fails its second part, day 2 is not part of the run, day 3 is new.
+        update_content(
+            &mut s,
+            vec![
+                timed(day!(1), Some("5.0ms"), None),
+                timed(day!(3), Some("6.0ms"), None),
+            ],
+            11.0,
+            &options,
+        )
+        .unwrap();
+
+        let expected = [
+            "foo",
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `5.0ms` | `2.0ms`† |",
+            "| [Day 2](./src/bin/02.rs) | `3.0ms` | `4.0ms` |",
+            "| [Day 3](./src/bin/03.rs) | `6.0ms` | `-` |",
+            "",
+            "† kept from a previous run, the last run did not produce this timing.",
+            "",
+            "**Total: 20.00ms**",
+            "<!--- benchmarking table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn refreshing_clears_stale_marker() {
+        let options = TableOptions::default();
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            vec![timed(day!(1), Some("1.0ms"), Some("2.0ms"))],
+            3.0,
+            &options,
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            vec![timed(day!(1), Some("1.0ms"), None)],
+            1.0,
+            &options,
+        )
+        .unwrap();
+        assert!(s.contains("`2.0ms`†"));
+
+        update_content(
+            &mut s,
+            vec![timed(day!(1), Some("1.0ms"), Some("2.5ms"))],
+            3.5,
+            &options,
+        )
+        .unwrap();
+        assert!(s.contains("| `1.0ms` | `2.5ms` |"));
+        assert!(!s.contains('†'));
+    }
+
+    #[test]
+    fn keeps_optional_columns_when_merging() {
+        let options = TableOptions {
+            columns: vec![Column::Title, Column::Samples],
+            ..TableOptions::default()
+        };
+        let mut first = timed(day!(5), Some("1.0ms"), Some("2.0ms"));
+        first.title = Some("If You | Give".into());
+        first.part_2_samples = Some(42);
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, vec![first], 3.0, &options).unwrap();
+        update_content(
+            &mut s,
+            vec![timed(day!(5), Some("1.0ms"), None)],
+            1.0,
+            &options,
+        )
+        .unwrap();
+
+        assert!(s.contains(
+            "| [Day 5](./src/bin/05.rs) | If You \\| Give | `1.0ms` | `2.0ms`† | - / 42 |"
+        ));
+    }
 }
@@ -1,5 +1,5 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, debug, read_file, Context, Day, Solution, ANSI_ITALIC, ANSI_RESET};
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
@@ -9,19 +9,135 @@ use std::{cmp, env, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// What a solution part may return: `Option<T>` if it only matters whether there is an
+/// answer, `Result<T, E>` if a failure has something to say, e.g. a [`crate::parse::ParseError`].
+pub trait PartResult {
+    type Answer: Display;
+
+    /// The answer, or the reason there is none.
+    fn into_answer(self) -> Result<Self::Answer, Option<String>>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<T, Option<String>> {
+        self.ok_or(None)
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<T, Option<String>> {
+        self.map_err(|err| Some(err.to_string()))
+    }
+}
+
+/// Marks a solution part taking only the input.
+pub struct InputOnly;
+/// Marks a solution part taking the input and a [`Context`].
+pub struct WithContext;
+
+/// A solution part, with or without a [`Context`] parameter. `M` is one of the markers
+/// [`InputOnly`] and [`WithContext`] and is inferred from the function.
+pub trait Part<M> {
+    type Output: PartResult;
+
+    fn solve(&self, input: &str, ctx: &Context) -> Self::Output;
+}
+
+impl<F: Fn(&str) -> R, R: PartResult> Part<InputOnly> for F {
+    type Output = R;
+
+    fn solve(&self, input: &str, _ctx: &Context) -> R {
+        self(input)
+    }
+}
+
+impl<F: Fn(&str, &Context) -> R, R: PartResult> Part<WithContext> for F {
+    type Output = R;
+
+    fn solve(&self, input: &str, ctx: &Context) -> R {
+        self(input, ctx)
+    }
+}
+
+/// Reads the arguments of the solution binary and the input of `day`, exiting with a
+/// message if either is unusable.
+pub fn setup(day: Day) -> (Context, String) {
+    let ctx = Context::from_args().unwrap_or_else(|err| {
+        eprintln!("{err}");
+        process::exit(1);
+    });
+    debug::set_verbosity(ctx.verbosity());
+    let folder = if ctx.is_example() {
+        "examples"
+    } else {
+        "inputs"
+    };
+    let input = read_file(folder, day).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        if err.is_missing_or_empty() && !ctx.is_example() {
+            eprintln!("Download it with `cargo download {day}`.");
+        }
+        process::exit(1);
+    });
+    (ctx, input)
+}
+
+/// Runs a [`Solution`]: prints its title if set, then runs its parser on its own, its export
+/// and both parts. Like free functions, the parts are timed including the parsing, so that the
+/// totals of all days compare.
+pub fn run_solution<S: Solution>(input: &str, ctx: &Context) {
+    if let Some(title) = S::TITLE {
+        println!("Title: {title}");
+    }
+    run_parse(S::parse, input);
+    run_export(|input, ctx| S::export(&S::parse(input), ctx), input, ctx);
+    run_part(S::solve_part_one, input, ctx, S::DAY, 1);
+    run_part(S::solve_part_two, input, ctx, S::DAY, 2);
+}
+
+pub fn run_part<M>(func: impl Part<M>, input: &str, ctx: &Context, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, samples) = run_timed(
+        |input| func.solve(input, ctx).into_answer(),
+        input,
+        |result| print_result(result, &part_str, ""),
+    );
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let Ok(result) = result {
+        submit_result(result, ctx, day, part);
+    }
+}
+
+/// Runs the export of a solution, e.g. writing a graph of the input to a file named by a
+/// parameter. It is called once before the parts, outside of the timings, and a failure is
+/// reported without stopping the run.
+pub fn run_export<E: Display>(
+    func: impl Fn(&str, &Context) -> Result<(), E>,
+    input: &str,
+    ctx: &Context,
+) {
+    // A copy of the context, so that the parameters read by the export do not keep the
+    // answers from being submitted.
+    if let Err(err) = func(input, &ctx.clone()) {
+        eprintln!("Export failed: {err}");
     }
 }
 
+/// Run the parser of a solution on its own, so that its time can be reported separately from the parts.
+pub fn run_parse<I: Clone, T>(func: impl Fn(I) -> T, input: I) {
+    let (_, duration, samples) = run_timed(func, input, |_| print!("Parse: ✔"));
+
+    print!("\r");
+    println!("Parse: ✔{}", format_duration(&duration, samples));
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
@@ -51,15 +167,11 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
+    let _quiet = debug::silence();
 
     for _ in 0..bench_iterations {
         // need a clone here to make the borrow checker happy.
@@ -92,11 +204,11 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result<T: Display>(result: &Result<T, Option<String>>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Ok(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -116,12 +228,15 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Err(reason) => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
                 print!("\r");
                 println!("{part}: ✖             ");
+                if let Some(reason) = reason {
+                    println!("{reason}");
+                }
             }
         }
     }
@@ -130,8 +245,10 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
+///  3. the answer is for the real input, not the example or a run whose parts read a `--param`.
 fn submit_result<T: Display>(
     result: T,
+    ctx: &Context,
     day: Day,
     part: u8,
 ) -> Option<Result<Output, aoc_cli::AocCommandError>> {
@@ -157,6 +274,20 @@ fn submit_result<T: Display>(
         return None;
     }
 
+    if ctx.is_example() {
+        eprintln!("Not submitting the answer to the example, run `cargo solve {day} --submit {part}` to submit.");
+        process::exit(1);
+    }
+
+    let used = ctx.used_params();
+    if !used.is_empty() {
+        eprintln!(
+            "Not submitting an answer depending on --param {}, run without it to submit.",
+            used.join(", ")
+        );
+        process::exit(1);
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
@@ -0,0 +1,70 @@
+use crate::template::{runner::PartResult, Context, Day};
+
+/// A day's solution as a type, an alternative to the free functions `part_one` and
+/// `part_two`. The input is parsed once into [`Solution::Input`], which both parts share.
+///
+/// The binary of the day is set up with `advent_of_code::solution!(solution = Type);`, and
+/// solutions can be called generically, e.g. in tests:
+///
+/// ```
+/// # use advent_of_code::{day, template::{Context, Day, Solution}};
+/// struct Sums;
+///
+/// impl Solution for Sums {
+///     const DAY: Day = day!(1);
+///     const TITLE: Option<&'static str> = Some("Sums");
+///
+///     type Input = Vec<u32>;
+///     type PartOne = Option<u32>;
+///     type PartTwo = Option<u32>;
+///
+///     fn parse(input: &str) -> Self::Input {
+///         input.lines().map(|line| line.parse().unwrap()).collect()
+///     }
+///
+///     fn part_one(input: &Self::Input, _ctx: &Context) -> Self::PartOne {
+///         Some(input.iter().sum())
+///     }
+///
+///     fn part_two(input: &Self::Input, _ctx: &Context) -> Self::PartTwo {
+///         input.iter().max().copied()
+///     }
+/// }
+///
+/// assert_eq!(Sums::solve_part_one("1\n2\n", &Context::example()), Some(3));
+/// ```
+pub trait Solution {
+    const DAY: Day;
+    /// The title of the puzzle, if known.
+    const TITLE: Option<&'static str> = None;
+
+    /// The parsed input.
+    type Input;
+    /// What part one returns, see [`PartResult`].
+    type PartOne: PartResult;
+    /// What part two returns, e.g. `Option<u32>` returning `None` until it is solved.
+    type PartTwo: PartResult;
+
+    fn parse(input: &str) -> Self::Input;
+
+    fn part_one(input: &Self::Input, ctx: &Context) -> Self::PartOne;
+
+    fn part_two(input: &Self::Input, ctx: &Context) -> Self::PartTwo;
+
+    /// Writes files asked for with `--param`, e.g. a graph of the input. It runs once before
+    /// the parts, so that benching them does not repeat it, see
+    /// [`run_export`](crate::template::runner::run_export).
+    fn export(_input: &Self::Input, _ctx: &Context) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Parses `input` and solves part one.
+    fn solve_part_one(input: &str, ctx: &Context) -> Self::PartOne {
+        Self::part_one(&Self::parse(input), ctx)
+    }
+
+    /// Parses `input` and solves part two.
+    fn solve_part_two(input: &str, ctx: &Context) -> Self::PartTwo {
+        Self::part_two(&Self::parse(input), ctx)
+    }
+}
@@ -0,0 +1,359 @@
+/// Saving frames to files: an asciinema cast of the whole animation, or one image per frame.
+///
+/// Everything is encoded by hand, the formats are simple enough: casts are JSON lines
+/// (asciicast v2), PPM images are raw RGB pixels and SVG images are text.
+use std::{
+    fmt::{Display, Write as _},
+    io::{self, Write},
+    str::FromStr,
+};
+
+use super::{Color, Frame};
+
+/// Side length of a cell in PPM images, in pixels.
+pub const PPM_CELL_SIZE: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExportFormat {
+    /// All frames in one asciinema `.cast` file.
+    Cast,
+    /// One binary PPM image per frame, each cell a square of its colour.
+    Ppm,
+    /// One SVG image per frame, showing the characters.
+    Svg,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [ExportFormat::Cast, ExportFormat::Ppm, ExportFormat::Svg];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Cast => "cast",
+            ExportFormat::Ppm => "ppm",
+            ExportFormat::Svg => "svg",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = UnknownFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ExportFormat::ALL
+            .into_iter()
+            .find(|format| format.extension() == s)
+            .ok_or_else(|| UnknownFormatError(s.to_string()))
+    }
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+#[derive(Debug)]
+pub struct UnknownFormatError(String);
+
+impl Display for UnknownFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown export format \"{}\", expected cast, ppm or svg.",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for UnknownFormatError {}
+
+impl Color {
+    /// The colour as red, green and blue, matching a dark terminal theme.
+    pub fn rgb(self) -> [u8; 3] {
+        match self {
+            Color::Default => [204, 204, 204],
+            Color::Gray => [118, 118, 118],
+            Color::Red => [231, 72, 86],
+            Color::Green => [22, 198, 12],
+            Color::Yellow => [249, 241, 165],
+            Color::Blue => [59, 120, 255],
+            Color::Magenta => [180, 0, 158],
+            Color::Cyan => [97, 214, 214],
+            Color::White => [242, 242, 242],
+        }
+    }
+}
+
+const BACKGROUND: [u8; 3] = [12, 12, 12];
+
+/// Whether a character leaves its cell empty in images without text.
+fn is_blank(c: char) -> bool {
+    matches!(c, ' ' | '.')
+}
+
+/// The frame as binary PPM image, each cell a `cell_size` square of its colour. Cells showing
+/// `.` or a space are drawn at a quarter of their brightness.
+pub fn encode_ppm(frame: &Frame, cell_size: usize) -> Vec<u8> {
+    let (width, height) = (frame.cells.width(), frame.cells.height());
+    let mut out = format!("P6\n{} {}\n255\n", width * cell_size, height * cell_size).into_bytes();
+
+    for row in frame.cells.rows() {
+        let pixels: Vec<u8> = row
+            .iter()
+            .flat_map(|&(c, color)| {
+                let rgb = if is_blank(c) {
+                    color.rgb().map(|v| v / 4)
+                } else {
+                    color.rgb()
+                };
+                rgb.repeat(cell_size)
+            })
+            .collect();
+        for _ in 0..cell_size {
+            out.extend_from_slice(&pixels);
+        }
+    }
+    out
+}
+
+/// The frame as SVG image with one line of monospace text per row, the caption below.
+pub fn encode_svg(frame: &Frame) -> String {
+    const CHAR_WIDTH: usize = 8;
+    const LINE_HEIGHT: usize = 16;
+
+    let mut lines: Vec<Vec<(char, Color)>> = frame.cells.rows().map(<[_]>::to_vec).collect();
+    if !frame.caption.is_empty() {
+        lines.push(frame.caption.chars().map(|c| (c, Color::Default)).collect());
+    }
+    let columns = lines.iter().map(Vec::len).max().unwrap_or(0);
+    let rgb = |color: Color| {
+        let [r, g, b] = color.rgb();
+        format!("#{r:02x}{g:02x}{b:02x}")
+    };
+
+    let mut svg = String::new();
+    let (width, height) = (columns * CHAR_WIDTH, lines.len() * LINE_HEIGHT);
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+    let [r, g, b] = BACKGROUND;
+    let _ = writeln!(
+        svg,
+        r#"<rect width="100%" height="100%" fill="rgb({r},{g},{b})"/>"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"<g font-family="monospace" font-size="14" xml:space="preserve">"#
+    );
+    for (i, line) in lines.iter().enumerate() {
+        let _ = write!(svg, r#"<text y="{}">"#, (i + 1) * LINE_HEIGHT - 4);
+        // one tspan per run of the same colour
+        let mut x = 0;
+        for run in line.chunk_by(|a, b| a.1 == b.1) {
+            let text: String = run.iter().map(|&(c, _)| xml_escape(c)).collect();
+            let _ = write!(
+                svg,
+                r#"<tspan x="{}" fill="{}">{text}</tspan>"#,
+                x * CHAR_WIDTH,
+                rgb(run[0].1)
+            );
+            x += run.len();
+        }
+        svg.push_str("</text>\n");
+    }
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+fn xml_escape(c: char) -> String {
+    match c {
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        '&' => "&amp;".to_string(),
+        c => c.to_string(),
+    }
+}
+
+/// Writes frames as an asciicast v2 recording, which `asciinema play` replays.
+pub struct CastWriter<W: Write> {
+    out: W,
+    fps: u32,
+    frames: usize,
+}
+
+impl<W: Write> CastWriter<W> {
+    /// Starts a recording of a `width` by `height` terminal, showing `fps` frames a second.
+    pub fn new(mut out: W, width: usize, height: usize, fps: u32) -> io::Result<Self> {
+        writeln!(
+            out,
+            r#"{{"version": 2, "width": {width}, "height": {height}, "env": {{"TERM": "xterm-256color"}}}}"#
+        )?;
+        Ok(CastWriter {
+            out,
+            fps: fps.max(1),
+            frames: 0,
+        })
+    }
+
+    /// Appends `frame`, timed by its index rather than the wall clock, so recordings of
+    /// slow solutions still play smoothly.
+    pub fn write(&mut self, frame: &Frame) -> io::Result<()> {
+        let time = self.frames as f64 / f64::from(self.fps);
+        let screen = format!("\x1b[H{}\x1b[J", frame.render().replace('\n', "\r\n"));
+        writeln!(self.out, "[{time:.6}, \"o\", {}]", json_string(&screen))?;
+        self.frames += 1;
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Saves every frame in the chosen formats to a directory.
+#[cfg(feature = "visualize")]
+pub(super) struct Exporter {
+    dir: std::path::PathBuf,
+    formats: Vec<ExportFormat>,
+    fps: u32,
+    cast: Option<CastWriter<io::BufWriter<std::fs::File>>>,
+    frames: usize,
+}
+
+#[cfg(feature = "visualize")]
+impl Exporter {
+    pub fn new(options: &super::Export, fps: u32) -> io::Result<Self> {
+        std::fs::create_dir_all(&options.dir)?;
+        Ok(Exporter {
+            dir: options.dir.clone(),
+            formats: options.formats.clone(),
+            fps,
+            cast: None,
+            frames: 0,
+        })
+    }
+
+    pub fn write(&mut self, frame: &Frame) -> io::Result<()> {
+        self.frames += 1;
+        for &format in &self.formats {
+            let path = || self.dir.join(format!("frame-{:05}.{format}", self.frames));
+            match format {
+                ExportFormat::Cast => {
+                    if self.cast.is_none() {
+                        let file = std::fs::File::create(self.dir.join("animation.cast"))?;
+                        // leave room for the caption and the status line
+                        let (width, height) = (frame.cells.width(), frame.cells.height() + 2);
+                        let width = width.max(frame.caption.chars().count());
+                        self.cast = Some(CastWriter::new(
+                            io::BufWriter::new(file),
+                            width,
+                            height,
+                            self.fps,
+                        )?);
+                    }
+                    self.cast.as_mut().unwrap().write(frame)?;
+                }
+                ExportFormat::Ppm => std::fs::write(path(), encode_ppm(frame, PPM_CELL_SIZE))?,
+                ExportFormat::Svg => std::fs::write(path(), encode_svg(frame))?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Flushes the recording and reports what was written.
+    pub fn finish(&mut self) -> io::Result<()> {
+        if let Some(cast) = self.cast.take() {
+            cast.into_inner().flush()?;
+        }
+        if self.frames > 0 {
+            let formats: Vec<String> = self.formats.iter().map(ToString::to_string).collect();
+            eprintln!(
+                "Exported {} frames as {} to {}",
+                self.frames,
+                formats.join(", "),
+                self.dir.display()
+            );
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{
+        direction::Pos,
+        visualize::{Color, Frame},
+    };
+
+    use super::{encode_ppm, encode_svg, CastWriter, ExportFormat};
+
+    fn frame() -> Frame {
+        let mut frame = Frame::new(2, 1, '.');
+        frame.set(Pos::new(1, 0), '<', Color::Red);
+        frame.with_caption("step \"1\"")
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!("svg".parse::<ExportFormat>().unwrap(), ExportFormat::Svg);
+        assert!("gif".parse::<ExportFormat>().is_err());
+    }
+
+    #[test]
+    fn ppm() {
+        let ppm = encode_ppm(&frame(), 2);
+        let header = b"P6\n4 2\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        let pixels = &ppm[header.len()..];
+        assert_eq!(pixels.len(), 4 * 2 * 3);
+        // a dimmed dot, then the red cell, twice per row
+        assert_eq!(&pixels[..3], &[51, 51, 51]);
+        assert_eq!(&pixels[6..9], &Color::Red.rgb());
+        assert_eq!(&pixels[..12], &pixels[12..]);
+    }
+
+    #[test]
+    fn svg() {
+        let svg = encode_svg(&frame());
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"64\""));
+        assert!(svg.contains("<tspan x=\"8\" fill=\"#e74856\">&lt;</tspan>"));
+        assert!(svg.contains(">step \"1\"</tspan>"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn cast() {
+        let mut cast = CastWriter::new(vec![], 2, 3, 4).unwrap();
+        cast.write(&frame()).unwrap();
+        cast.write(&frame()).unwrap();
+        let text = String::from_utf8(cast.into_inner()).unwrap();
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with(r#"{"version": 2, "width": 2, "height": 3"#));
+        assert!(lines[1]
+            .starts_with(r#"[0.000000, "o", "\u001b[H.\u001b[91m<\u001b[0m\r\nstep \"1\"\r\n"#));
+        assert!(lines[2].starts_with("[0.250000, "));
+    }
+}
@@ -0,0 +1,250 @@
+/// Terminal animations of grid simulations.
+///
+/// A solution asks its [`Context`](crate::template::Context) for a [`Visualizer`] and feeds
+/// it one [`Frame`] per step of the simulation. Under `cargo solve DD --visualize` the frames
+/// are played in the terminal: `space` pauses, `n` steps one frame while paused, `+` and `-`
+/// change the frame rate and `q` skips the rest of the animation. The initial frame rate is
+/// set with `--param fps=<n>`.
+///
+/// With `--export <dir>` the frames are saved instead, see [`export`] for the formats.
+///
+/// Without the `visualize` feature, which only `--visualize` and `--export` enable, a
+/// [`Visualizer`] is an empty struct and frames are never built, so timed runs do not pay
+/// for any of this.
+use std::{fmt::Write, path::PathBuf};
+
+use crate::{direction::Pos, grid::Grid};
+
+pub mod export;
+#[cfg(feature = "visualize")]
+mod player;
+
+pub use export::ExportFormat;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Color {
+    #[default]
+    Default,
+    Gray,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    fn ansi(self) -> &'static str {
+        match self {
+            Color::Default => "\x1b[0m",
+            Color::Gray => "\x1b[90m",
+            Color::Red => "\x1b[91m",
+            Color::Green => "\x1b[92m",
+            Color::Yellow => "\x1b[93m",
+            Color::Blue => "\x1b[94m",
+            Color::Magenta => "\x1b[95m",
+            Color::Cyan => "\x1b[96m",
+            Color::White => "\x1b[97m",
+        }
+    }
+}
+
+/// A single picture of a grid, each cell being a coloured character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    cells: Grid<(char, Color)>,
+    caption: String,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize, fill: char) -> Self {
+        Frame {
+            cells: Grid::new(width, height, (fill, Color::Default)),
+            caption: String::new(),
+        }
+    }
+
+    /// Draws every cell of `grid` with the character and colour `draw` picks for it.
+    pub fn from_grid<T>(grid: &Grid<T>, draw: impl FnMut(&T) -> (char, Color)) -> Self {
+        Frame {
+            cells: grid.map(draw),
+            caption: String::new(),
+        }
+    }
+
+    /// Draws over the cell at `pos`, ignoring positions outside the frame.
+    pub fn set(&mut self, pos: Pos, c: char, color: Color) {
+        if let Some(cell) = self.cells.get_mut(pos) {
+            *cell = (c, color);
+        }
+    }
+
+    /// Recolours the cell at `pos`, keeping its character.
+    pub fn paint(&mut self, pos: Pos, color: Color) {
+        if let Some(cell) = self.cells.get_mut(pos) {
+            cell.1 = color;
+        }
+    }
+
+    /// A line of text shown below the grid, e.g. the current step.
+    #[must_use]
+    pub fn with_caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = caption.into();
+        self
+    }
+
+    /// The frame as text with ANSI colour codes, one line per row.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for row in self.cells.rows() {
+            let mut current = Color::Default;
+            for &(c, color) in row {
+                if color != current {
+                    out.push_str(color.ansi());
+                    current = color;
+                }
+                out.push(c);
+            }
+            if current != Color::Default {
+                out.push_str(Color::Default.ansi());
+            }
+            out.push('\n');
+        }
+        if !self.caption.is_empty() {
+            let _ = writeln!(out, "{}", self.caption);
+        }
+        out
+    }
+}
+
+/// Where the frames of a run go.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VisualizerOptions {
+    /// Play the frames in the terminal.
+    pub play: bool,
+    pub fps: u32,
+    pub export: Option<Export>,
+}
+
+/// Save the frames to `dir`, in each of `formats`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Export {
+    pub dir: PathBuf,
+    pub formats: Vec<ExportFormat>,
+}
+
+/// Plays or exports frames, or does nothing if visualization is off.
+#[derive(Default)]
+pub struct Visualizer {
+    #[cfg(feature = "visualize")]
+    player: Option<player::Player>,
+    #[cfg(feature = "visualize")]
+    exporter: Option<export::Exporter>,
+}
+
+impl Visualizer {
+    /// A visualizer ignoring all frames.
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// A visualizer handling frames as `options` say, if the `visualize` feature is enabled.
+    #[allow(unused_variables)]
+    pub fn new(options: &VisualizerOptions) -> Self {
+        #[cfg(feature = "visualize")]
+        return Visualizer {
+            player: options
+                .play
+                .then(|| player::Player::start(options.fps).ok())
+                .flatten(),
+            exporter: options.export.as_ref().and_then(|export| {
+                export::Exporter::new(export, options.fps)
+                    .map_err(|err| eprintln!("Cannot export frames: {err}"))
+                    .ok()
+            }),
+        };
+        #[cfg(not(feature = "visualize"))]
+        Visualizer {}
+    }
+
+    /// Whether frames are used, to skip work only needed for them.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        #[cfg(feature = "visualize")]
+        return self.player.is_some() || self.exporter.is_some();
+        #[cfg(not(feature = "visualize"))]
+        false
+    }
+
+    /// Shows or saves the frame built by `frame`, which is only called if frames are used.
+    #[inline]
+    #[allow(unused_variables, unused_mut)]
+    pub fn frame(&mut self, frame: impl FnOnce() -> Frame) {
+        #[cfg(feature = "visualize")]
+        if self.is_enabled() {
+            let frame = frame();
+            if let Some(exporter) = &mut self.exporter {
+                if let Err(err) = exporter.write(&frame) {
+                    eprintln!("Cannot export frames: {err}");
+                    self.exporter = None;
+                }
+            }
+            if let Some(player) = &mut self.player {
+                if !player.show(&frame) {
+                    // quit by the user, the rest of the solution runs without animation
+                    self.player = None;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "visualize")]
+impl Drop for Visualizer {
+    fn drop(&mut self) {
+        // leave the alternate screen before reporting the export
+        self.player = None;
+        if let Some(mut exporter) = self.exporter.take() {
+            if let Err(err) = exporter.finish() {
+                eprintln!("Cannot export frames: {err}");
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{direction::Pos, grid::Grid};
+
+    use super::{Color, Frame, Visualizer};
+
+    #[test]
+    fn renders_colours() {
+        let grid = Grid::from_vec(3, vec![0, 1, 0, 1, 1, 0]);
+        let mut frame = Frame::from_grid(&grid, |&v| {
+            if v == 1 {
+                ('#', Color::Red)
+            } else {
+                ('.', Color::Default)
+            }
+        });
+        frame.set(Pos::new(2, 1), '@', Color::Default);
+        frame.set(Pos::new(5, 5), '@', Color::Default);
+        let frame = frame.with_caption("step 1");
+        assert_eq!(
+            frame.render(),
+            ".\x1b[91m#\x1b[0m.\n\x1b[91m##\x1b[0m@\nstep 1\n"
+        );
+    }
+
+    #[test]
+    fn disabled_never_builds_frames() {
+        let mut vis = Visualizer::disabled();
+        assert!(!vis.is_enabled());
+        vis.frame(|| unreachable!());
+    }
+}
@@ -0,0 +1,104 @@
+use std::{
+    io::{self, stdout, Write},
+    time::{Duration, Instant},
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, terminal,
+};
+
+use super::Frame;
+
+pub struct Player {
+    fps: u32,
+    paused: bool,
+    shown: usize,
+}
+
+enum Action {
+    Next,
+    Quit,
+}
+
+impl Player {
+    pub fn start(fps: u32) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Player {
+            fps: fps.max(1),
+            paused: false,
+            shown: 0,
+        })
+    }
+
+    /// Draws `frame` and waits for the next one to be due. Returns `false` if the user
+    /// quit the animation.
+    pub fn show(&mut self, frame: &Frame) -> bool {
+        self.shown += 1;
+        if self.draw(frame).is_err() {
+            return false;
+        }
+        matches!(self.wait(), Ok(Action::Next))
+    }
+
+    fn draw(&self, frame: &Frame) -> io::Result<()> {
+        let mut out = stdout().lock();
+        execute!(out, cursor::MoveTo(0, 0))?;
+        // raw mode does not return the cursor to the start of the line
+        write!(out, "{}", frame.render().replace('\n', "\r\n"))?;
+        let state = if self.paused { "paused" } else { "playing" };
+        write!(
+            out,
+            "frame {} · {} fps · {state} · space pause, n step, +/- speed, q quit",
+            self.shown, self.fps
+        )?;
+        execute!(out, terminal::Clear(terminal::ClearType::FromCursorDown))?;
+        out.flush()
+    }
+
+    fn wait(&mut self) -> io::Result<Action> {
+        let due = Instant::now() + Duration::from_secs(1) / self.fps;
+        loop {
+            let timeout = if self.paused {
+                Duration::from_secs(3600)
+            } else {
+                due.saturating_duration_since(Instant::now())
+            };
+            if !event::poll(timeout)? {
+                if self.paused {
+                    continue;
+                }
+                return Ok(Action::Next);
+            }
+            let Event::Key(KeyEvent {
+                code,
+                modifiers,
+                kind: KeyEventKind::Press,
+                ..
+            }) = event::read()?
+            else {
+                continue;
+            };
+            match code {
+                KeyCode::Char(' ') => self.paused = !self.paused,
+                KeyCode::Char('n') | KeyCode::Right if self.paused => return Ok(Action::Next),
+                KeyCode::Char('+') => self.fps = (self.fps * 2).min(960),
+                KeyCode::Char('-') => self.fps = (self.fps / 2).max(1),
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(Action::Quit),
+                KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(Action::Quit)
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Drop for Player {
+    fn drop(&mut self) {
+        let _ = execute!(stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}